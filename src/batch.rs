@@ -0,0 +1,65 @@
+// Batch mode: one command per line, same grammar as the command line. A
+// failing line is reported on stderr with its line number and the run goes on.
+
+use std::io::{self, BufRead, Write};
+
+use serde_json::json;
+
+use crate::cli;
+use crate::clock::Clock;
+use crate::render::{self, OutputFormat};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub failed: Vec<usize>,
+}
+
+pub fn run(
+    input: impl BufRead,
+    clock: &dyn Clock,
+    format: OutputFormat,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<BatchSummary> {
+    let mut summary = BatchSummary::default();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let number = index + 1;
+        summary.total += 1;
+
+        let result = cli::parse_line(line)
+            // Only the first line of a parser error, the usage block would drown the output
+            .map_err(|e| e.to_string().lines().next().unwrap_or_default().to_string())
+            .and_then(|cli| {
+                cli.execute_line(clock, format).map_err(|e| format!("ERROR: {}", e))
+            });
+
+        if let Ok((report, OutputFormat::Text)) = &result {
+            for warning in render::warnings(report) {
+                writeln!(err, "line {}: WARNING: {}", number, warning)?;
+            }
+        }
+
+        match result {
+            Ok((report, OutputFormat::Json)) => writeln!(out, "{}", render::json(&report))?,
+            Ok((report, OutputFormat::Text)) => write!(out, "{}", render::line(&report))?,
+            Err(message) => {
+                summary.failed.push(number);
+                if format == OutputFormat::Json {
+                    writeln!(err, "{}", json!({ "line": number, "error": message }))?;
+                } else {
+                    writeln!(err, "line {}: {}", number, message)?;
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
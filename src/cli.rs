@@ -0,0 +1,487 @@
+// Command line definition. Subcommands keep their free-form positional
+// grammar (`tz October 9, 2025 at 04:00AM UTC+8 to WIB`), clap only splits
+// off the command name and the global flags before handing the words over.
+
+use std::path::PathBuf;
+
+use chrono::Duration;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::ArgValueCompleter;
+
+use crate::clock::{Clock, FixedClock, SystemClock};
+use crate::completions::{self, CompletionShell};
+use crate::config::{self, Config, DstPolicy};
+use crate::render::{OutputFormat, Report};
+use crate::{
+    handle_day_of_week, handle_future_date, handle_now, handle_past_date, handle_remaining,
+    handle_meet, handle_team, handle_timezone_convert, handle_transitions, handle_where, handle_zone_info, handle_zones,
+    parse_duration, TimecalcError, WorkingHours,
+};
+
+const OVERVIEW: &str = "\
+FUTURE/PAST DATES:
+  timecalc future 69 days    - Calculate date 69 days from now
+  timecalc future 69d        - Short form
+  timecalc past 30 days      - Calculate date 30 days ago
+
+TIMEZONE CONVERSION:
+  timecalc convert 4:00 UTC+7 to WIB
+  timecalc convert 10:00 PST to WIB
+  timecalc tz 14:30 WIB to UTC
+  timecalc tz 14:30 WIB to JST, PST, UTC
+  timecalc tz now+3h WIB to UTC
+
+REMAINING DAYS:
+  timecalc remaining month   - Days left in current month
+  timecalc remaining year    - Days left in current year
+  timecalc left month        - Same as above
+
+WORLD CLOCK:
+  timecalc now               - Current time in WIB, WITA, WIT and UTC
+  timecalc now JST PST       - Or in the zones you name
+  timecalc team              - Who on your team is at work ([[team]] in the config)
+  timecalc meet WIB JST PST  - When everyone is inside working hours today
+
+DAY OF WEEK:
+  timecalc day 2025-12-25    - What day is this date?
+  timecalc day next friday   - Or a day relative to today
+
+SUPPORTED TIMEZONES:
+  Indonesia: WIB (UTC+7), WITA (UTC+8), WIT (UTC+9)
+  Southeast Asia: SGT (Singapore), MYT (Malaysia)
+  Common: UTC, PST (UTC-8), EST (UTC-5), JST (Japan)
+  Fixed offsets: UTC+7, UTC+5:30, GMT-3, +0545, -03:30
+  Military zones: 0400Z, 1600 Romeo, K, Zulu (print them with --military)
+  Cities and countries: Tokyo, Bandung, New York, JP (see 'timecalc where')
+  Any IANA name: Europe/Berlin, America/Sao_Paulo (see 'timecalc zones')
+  Offsets and DST changes of one zone: 'timecalc zone PST', 'timecalc transitions PST 2026'
+  Your own aliases: [aliases] in ~/.config/timecalc/config.toml
+
+Run 'timecalc help <COMMAND>' for the usage of a single command.";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "timecalc",
+    version,
+    about = "TIME CALCULATOR CLI - offline date math and timezone conversion",
+    after_help = OVERVIEW
+)]
+pub struct Cli {
+    /// Pretend the current time is DATETIME (2025-02-27, 2025-02-27 09:30 or RFC 3339)
+    #[arg(long, global = true, value_name = "DATETIME", env = "TIMECALC_NOW")]
+    pub now: Option<String>,
+
+    /// Print results as text or as a single JSON object [default: text, or `output` from the config file]
+    #[arg(long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Refuse ambiguous abbreviations such as CST or IST instead of picking one
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// How to read a local time skipped or repeated by a DST change [default: earliest, or `dst` from the config file]
+    #[arg(long, global = true, value_enum, value_name = "POLICY")]
+    pub dst: Option<DstPolicy>,
+
+    /// Print times in military notation with the zone letter, 0400Z or 1100G
+    #[arg(long, global = true)]
+    pub military: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Calculate the date N days from today
+    #[command(
+        visible_alias = "date",
+        after_help = "Examples:\n  timecalc future 69 days\n  timecalc future 69d\n  timecalc date 7"
+    )]
+    Future {
+        /// Number of days: 69, 69d, 69days or 69 days (-7 goes back)
+        #[arg(required = true, value_name = "DAYS", allow_hyphen_values = true)]
+        days: Vec<String>,
+    },
+
+    /// Calculate the date N days before today
+    #[command(after_help = "Examples:\n  timecalc past 30 days\n  timecalc past 7d")]
+    Past {
+        /// Number of days: 30, 30d, 30days or 30 days (-7 goes forward)
+        #[arg(required = true, value_name = "DAYS", allow_hyphen_values = true)]
+        days: Vec<String>,
+    },
+
+    /// Convert a time from one timezone to another
+    #[command(
+        visible_alias = "tz",
+        after_help = "Examples:\n  \
+            timecalc tz 4:00 UTC+7 to WIB\n  \
+            timecalc tz 04:00AM UTC+8 to WIB\n  \
+            timecalc tz October 9, 2025 at 04:00AM UTC+8 to WIB\n  \
+            timecalc convert 2025-10-09 04:00 UTC+8 to WIB\n  \
+            timecalc tz 09:00 WIB to JST, PST, Europe/Berlin\n  \
+            timecalc tz now to JST\n  \
+            timecalc tz in 90 minutes to PST\n  \
+            timecalc tz now+3h WIB to UTC\n  \
+            timecalc tz 2025-03-09 02:30 PST to UTC --dst shift-forward"
+    )]
+    Convert {
+        /// [DATE] TIME FROM_ZONE to TO_ZONE [, TO_ZONE...], or now[+3h] / in 90 minutes [FROM_ZONE] for TIME
+        #[arg(
+            required = true,
+            allow_hyphen_values = true,
+            value_name = "EXPRESSION",
+            add = ArgValueCompleter::new(completions::complete_expression)
+        )]
+        expression: Vec<String>,
+    },
+
+    /// Days left (and passed) in the current month or year
+    #[command(
+        visible_alias = "left",
+        after_help = "Examples:\n  timecalc remaining month\n  timecalc left year"
+    )]
+    Remaining {
+        #[arg(value_parser = ["month", "year"], ignore_case = true)]
+        period: String,
+    },
+
+    /// What day of the week a date falls on
+    #[command(after_help = "Examples:\n  \
+        timecalc day 2025-12-25\n  \
+        timecalc day tomorrow\n  \
+        timecalc day next friday\n  \
+        timecalc day in 2 weeks\n  \
+        timecalc day end of month")]
+    Day {
+        /// Date as YYYY-MM-DD or October 9, 2025, or relative: today, tomorrow,
+        /// yesterday, [this|next|last] friday, in 2 weeks, 3 days ago, end of month
+        #[arg(required = true, value_name = "DATE")]
+        date: Vec<String>,
+    },
+
+    /// Print a shell completion script
+    #[command(after_help = "Examples:\n  \
+        echo 'source <(timecalc completions bash)' >> ~/.bashrc\n  \
+        echo 'source <(timecalc completions zsh)' >> ~/.zshrc\n  \
+        timecalc completions fish > ~/.config/fish/completions/timecalc.fish")]
+    Completions {
+        shell: CompletionShell,
+    },
+
+    /// Interactive prompt, `_` refers to the previous result
+    #[command(after_help = "Examples:\n  \
+        timecalc> tz 4:00 UTC+7 to WIB\n  \
+        timecalc> _ to JST\n  \
+        timecalc> _ + 3d\n  \
+        timecalc> future 30d\n\n\
+        History is kept in TIMECALC_HISTORY or the platform data directory.")]
+    Repl,
+
+    /// Run one command per line from FILE (or stdin), one result per line
+    #[command(after_help = "Examples:\n  \
+        timecalc batch events.txt\n  \
+        printf 'tz 2025-10-09 04:00 UTC+8 to WIB\\nday 2025-12-25\\n' | timecalc batch\n  \
+        timecalc batch events.txt --output json | jq .to.datetime\n\n\
+        Blank lines and lines starting with # are skipped. Failing lines are\n\
+        reported on stderr with their line number and do not stop the run.")]
+    Batch {
+        /// File with one command per line, stdin when omitted or '-'
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        file: Option<PathBuf>,
+    },
+
+    /// Which timezone a city or country is in, and the time there now
+    #[command(after_help = "Examples:\n  \
+        timecalc where Bandung\n  \
+        timecalc where New York\n  \
+        timecalc where JP\n  \
+        timecalc where Indonesia")]
+    Where {
+        /// City, country name or two-letter country code
+        #[arg(required = true, value_name = "PLACE")]
+        place: Vec<String>,
+    },
+
+    /// A zone's IANA name, standard and DST offsets, and its previous and next change
+    #[command(after_help = "Examples:\n  \
+        timecalc zone PST\n  \
+        timecalc zone Europe/Berlin\n  \
+        timecalc zone New York --now 2025-12-01")]
+    Zone {
+        /// Any timezone name, abbreviation, offset, city or country
+        #[arg(required = true, value_name = "NAME", add = ArgValueCompleter::new(completions::complete_zone))]
+        name: Vec<String>,
+    },
+
+    /// Every UTC offset change of a zone in a year (this year by default) or a range
+    #[command(after_help = "Examples:\n  \
+        timecalc transitions PST\n  \
+        timecalc transitions Europe/Berlin 2026\n  \
+        timecalc transitions New York 2020 2025\n  \
+        timecalc transitions Asia/Jakarta 1900-01-01 2000-12-31")]
+    Transitions {
+        /// ZONE [YEAR | FROM TO], FROM and TO as years or YYYY-MM-DD
+        #[arg(required = true, value_name = "ZONE", add = ArgValueCompleter::new(completions::complete_zone))]
+        args: Vec<String>,
+    },
+
+    /// List the IANA timezones with their current offset
+    #[command(after_help = "Examples:\n  \
+        timecalc zones\n  \
+        timecalc zones --search asia\n  \
+        timecalc zones --search \"new york\"")]
+    Zones {
+        /// Only zones whose name contains TEXT (case-insensitive)
+        #[arg(long, short, value_name = "TEXT")]
+        search: Option<String>,
+    },
+
+    /// The current time (or --at) in several zones, and whether people there are at work
+    #[command(
+        visible_alias = "clock",
+        after_help = "Examples:\n  \
+            timecalc now\n  \
+            timecalc now JST PST New York\n  \
+            timecalc clock WIB, Europe/Berlin --at \"2025-10-09 09:00 WIB\"\n\n\
+            Without zones it shows clock_zones from the config file, or WIB, WITA, WIT and UTC.\n\
+            Working hours are 09:00-17:00 on weekdays, daytime 06:00-18:00."
+    )]
+    Now {
+        /// Zones to show, with or without commas
+        #[arg(value_name = "ZONES", add = ArgValueCompleter::new(completions::complete_zone))]
+        zones: Vec<String>,
+
+        /// Show this instant instead of now: a date and time with or without a zone, or RFC 3339
+        #[arg(long, value_name = "DATETIME")]
+        at: Option<String>,
+    },
+
+    /// Everyone on the team roster: their local time, and whether they are at work
+    #[command(after_help = "Examples:\n  \
+        timecalc team\n  \
+        timecalc team --at \"2025-10-09 09:00 WIB\"\n\n\
+        The roster lives in the config file, one [[team]] entry per person:\n  \
+        [[team]]\n  \
+        name = \"Ana\"\n  \
+        zone = \"WIB\"\n  \
+        hours = \"08:00-16:00\"              # default 09:00-17:00\n  \
+        weekend = [\"friday\", \"saturday\"]  # default Saturday and Sunday")]
+    Team {
+        /// Show this instant instead of now: a date and time with or without a zone, or RFC 3339
+        #[arg(long, value_name = "DATETIME")]
+        at: Option<String>,
+    },
+
+    /// Find the times on one day when everyone is inside working hours
+    #[command(after_help = "Examples:\n  \
+        timecalc meet WIB JST PST\n  \
+        timecalc meet WIB, Europe/Berlin --date 2025-10-20 --duration 30m\n  \
+        timecalc meet Jakarta Tokyo --hours 08:00-18:00\n\n\
+        The day is the date in the first zone. Slots start every half hour and are\n\
+        ranked by how far they stay from anyone's start or end of day.")]
+    Meet {
+        /// Zones of the participants, with or without commas
+        #[arg(required = true, value_name = "ZONES", add = ArgValueCompleter::new(completions::complete_zone))]
+        zones: Vec<String>,
+
+        /// Day of the meeting in the first zone, YYYY-MM-DD or tomorrow, next monday, ... [default: today there]
+        #[arg(long, value_name = "DATE")]
+        date: Option<String>,
+
+        /// Working hours in every zone's local time
+        #[arg(long, value_name = "HH:MM-HH:MM", default_value = "09:00-17:00", value_parser = hours_arg)]
+        hours: WorkingHours,
+
+        /// Length of the meeting: 30m, 1h, 90 minutes
+        #[arg(long, value_name = "LENGTH", default_value = "1h", value_parser = length_arg)]
+        duration: Duration,
+    },
+
+    /// Inspect the configuration file (TIMECALC_CONFIG or ~/.config/timecalc/config.toml)
+    #[command(after_help = "Examples:\n  \
+        timecalc config show\n\n\
+        Example config.toml:\n  \
+        default_zone = \"WIB\"\n  \
+        output = \"text\"\n  \
+        time_format = \"12h\"\n  \
+        week_start = \"sunday\"\n  \
+        dst = \"earliest\"\n\n  \
+        [aliases]\n  \
+        office = \"Asia/Jakarta\"")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective configuration, defaults filled in
+    Show,
+}
+
+impl Cli {
+    // --output wins over the config file
+    pub fn format(&self) -> OutputFormat {
+        self.output.unwrap_or(config::active().output)
+    }
+
+    // The active configuration with --strict, --dst and --military applied
+    pub fn configured(&self) -> Config {
+        let mut config = config::active().as_ref().clone();
+        config.strict |= self.strict;
+        config.military |= self.military;
+        config.dst = self.dst.unwrap_or(config.dst);
+        config
+    }
+
+    // --now (or TIMECALC_NOW) pins the clock, so results can be replayed at any date
+    pub fn clock(&self) -> Result<Box<dyn Clock>, TimecalcError> {
+        match &self.now {
+            Some(value) => FixedClock::parse(value)
+                .map(|clock| Box::new(clock) as Box<dyn Clock>)
+                .ok_or_else(|| TimecalcError::InvalidDateTime(value.clone())),
+            None => Ok(Box::new(SystemClock)),
+        }
+    }
+
+    // Run a line read by the REPL or batch mode: its own --now wins over the
+    // session clock, and its own --output over the session format
+    pub fn execute_line(
+        &self,
+        clock: &dyn Clock,
+        format: OutputFormat,
+    ) -> Result<(Report, OutputFormat), TimecalcError> {
+        let format = self.output.unwrap_or(format);
+        let command = match &self.command {
+            Some(command) => command,
+            None => return Ok((Report::Script(Cli::command().render_help().to_string()), format)),
+        };
+
+        // This line's --strict, --dst and --military only apply to this line
+        let session = config::active();
+        config::install(self.configured());
+        let report = match &self.now {
+            Some(_) => self.clock().and_then(|clock| execute(command, clock.as_ref())),
+            None => execute(command, clock),
+        };
+        config::install(session.as_ref().clone());
+        Ok((report?, format))
+    }
+}
+
+// One line of REPL or batch input, split into words like a shell would for
+// the free-form grammar
+pub fn parse_line(line: &str) -> Result<Cli, clap::Error> {
+    let mut args = vec!["timecalc".to_string()];
+    args.extend(line.split_whitespace().map(str::to_string));
+    Cli::try_parse_from(normalize_args(args))
+}
+
+pub fn execute(command: &Command, clock: &dyn Clock) -> Result<Report, TimecalcError> {
+    match command {
+        Command::Future { days } => handle_future_date(days, clock).map(Report::Date),
+        Command::Past { days } => handle_past_date(days, clock).map(Report::Date),
+        Command::Convert { expression } => {
+            handle_timezone_convert(expression, clock).map(Report::Conversion)
+        }
+        Command::Remaining { period } => {
+            handle_remaining(std::slice::from_ref(period), clock).map(Report::Remaining)
+        }
+        Command::Day { date } => handle_day_of_week(date, clock).map(Report::Day),
+        Command::Completions { shell } => {
+            let mut script = Vec::new();
+            completions::write_registration(*shell, &mut script)
+                .expect("writing to a Vec cannot fail");
+            Ok(Report::Script(String::from_utf8_lossy(&script).into_owned()))
+        }
+        Command::Where { place } => handle_where(place, clock).map(Report::Where),
+        Command::Now { zones, at } => handle_now(zones, at.as_deref(), clock).map(Report::Clock),
+        Command::Meet { zones, date, hours, duration } => {
+            handle_meet(zones, date.as_deref(), *hours, *duration, clock).map(Report::Meeting)
+        }
+        Command::Team { at } => handle_team(at.as_deref(), clock).map(Report::Team),
+        Command::Zone { name } => handle_zone_info(name, clock).map(Report::ZoneInfo),
+        Command::Transitions { args } => handle_transitions(args, clock).map(Report::Transitions),
+        Command::Zones { search } => Ok(Report::Zones(handle_zones(search.as_deref(), clock))),
+        Command::Config { action: ConfigAction::Show } => {
+            Ok(Report::Config(config::active().as_ref().clone()))
+        }
+        // These read their own input, only the binary itself can start them
+        Command::Repl => Err(TimecalcError::NotAvailable("repl")),
+        Command::Batch { .. } => Err(TimecalcError::NotAvailable("batch")),
+    }
+}
+
+// --hours and --duration of `meet`, refused by clap like any other bad value
+fn hours_arg(value: &str) -> Result<WorkingHours, String> {
+    WorkingHours::parse(value).ok_or_else(|| "expected working hours like 09:00-17:00".to_string())
+}
+
+fn length_arg(value: &str) -> Result<Duration, String> {
+    parse_duration(value)
+        .filter(|length| *length > Duration::zero() && *length <= Duration::days(1))
+        .ok_or_else(|| "expected a length like 30m, 1h or 90 minutes, up to a day".to_string())
+}
+
+// Free-form expressions accept hyphen values such as "-03:30", which makes
+// clap treat every word after the first one as part of the expression. Move
+// global options and --help that follow the expression up to just behind the
+// command name, so `tz 4:00 UTC to WIB --output json` still works. Command
+// names match in any case, `TZ 10:00 WIB to JST` included.
+pub fn normalize_args(mut args: Vec<String>) -> Vec<String> {
+    let cli = Cli::command();
+    let takes_value = |token: &str| {
+        cli.get_arguments().any(|arg| {
+            arg.get_long() == Some(token.trim_start_matches('-'))
+                && arg.get_action().takes_values()
+        })
+    };
+    let is_global = |token: &str| {
+        let name = token.trim_start_matches('-');
+        let name = name.split('=').next().unwrap_or(name);
+        token.starts_with("--") && cli.get_arguments().any(|arg| arg.get_long() == Some(name))
+    };
+
+    // Skip the program name and any global options in front of the command
+    let mut command_pos = 1;
+    while command_pos < args.len() && args[command_pos].starts_with('-') {
+        command_pos += if takes_value(&args[command_pos]) { 2 } else { 1 };
+    }
+    if command_pos >= args.len() {
+        return args;
+    }
+    let command = args[command_pos].to_lowercase();
+    if cli.find_subcommand(&command).is_some() {
+        args[command_pos] = command;
+    }
+
+    let mut lifted = Vec::new();
+    let mut rest = Vec::new();
+    let mut iter = args[command_pos + 1..].iter();
+    while let Some(token) = iter.next() {
+        if token == "--help" || token == "-h" {
+            lifted.push(token.clone());
+        } else if is_global(token) {
+            lifted.push(token.clone());
+            if takes_value(token) {
+                lifted.extend(iter.next().cloned());
+            }
+        } else {
+            rest.push(token.clone());
+        }
+    }
+
+    let mut normalized = args[..=command_pos].to_vec();
+    normalized.extend(lifted);
+    normalized.extend(rest);
+    normalized
+}
+
+pub fn print_help() {
+    // Printing to a closed stdout is not worth reporting
+    let _ = Cli::command().print_help();
+    println!();
+}
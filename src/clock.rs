@@ -0,0 +1,54 @@
+// Source of "now" for everything that depends on the current date.
+// The CLI uses SystemClock unless --now / TIMECALC_NOW pins it to a FixedClock.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+pub trait Clock {
+    fn now(&self) -> DateTime<Local>;
+
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedClock(pub DateTime<Local>);
+
+impl FixedClock {
+    // Accepts RFC 3339 ("2025-02-27T09:30:00+07:00"), a local date and time
+    // ("2025-02-27 09:30", "2025-02-27T09:30:00") or a bare date (midnight)
+    pub fn parse(input: &str) -> Option<FixedClock> {
+        let input = input.trim();
+
+        if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+            return Some(FixedClock(dt.with_timezone(&Local)));
+        }
+
+        let formats = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"];
+        let naive = formats
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })?;
+
+        Local.from_local_datetime(&naive).earliest().map(FixedClock)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Local> {
+        self.0
+    }
+}
@@ -0,0 +1,92 @@
+// Shell completion. `timecalc completions <shell>` prints a small script that
+// calls back into timecalc (COMPLETE=<shell> timecalc -- ...) on every <TAB>,
+// so zone names are always the ones this binary actually accepts.
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::io::{self, Write};
+
+use chrono_tz::TZ_VARIANTS;
+use clap::ValueEnum;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Zsh};
+use clap_complete::CompletionCandidate;
+
+use crate::{config, places};
+use crate::{parse_timezone, LOCAL_KEYWORDS, TIMEZONE_ALIASES};
+
+// Environment variable the registration scripts use to call back into timecalc
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+pub fn write_registration(shell: CompletionShell, out: &mut dyn Write) -> io::Result<()> {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+    };
+
+    completer.write_registration(COMPLETE_VAR, "timecalc", "timecalc", "timecalc", out)
+}
+
+// User aliases, the local keywords, built-in aliases, every IANA name
+// parse_timezone accepts, then cities and single-zone countries
+pub fn zone_names() -> Vec<String> {
+    let mut names: Vec<String> = config::active().aliases.keys().cloned().collect();
+    let mut seen: HashSet<String> = names.iter().cloned().collect();
+
+    for keyword in LOCAL_KEYWORDS {
+        if !names.iter().any(|name| name.eq_ignore_ascii_case(keyword)) {
+            names.push(keyword.to_string());
+            seen.insert(keyword.to_string());
+        }
+    }
+    // Every IANA name resolves, no need to ask parse_timezone
+    let builtin = TIMEZONE_ALIASES.iter().map(|(alias, _)| *alias).chain(TZ_VARIANTS.iter().map(|tz| tz.name()));
+    for name in builtin {
+        if seen.insert(name.to_string()) {
+            names.push(name.to_string());
+        }
+    }
+    // Single-zone places are known from the lookup alone, only countries
+    // spanning several zones need the full resolver (an alias may name them)
+    for place in places::names() {
+        if seen.contains(&place) {
+            continue;
+        }
+        let single = places::lookup(&place).is_some_and(|found| found.zones.len() == 1);
+        if single || parse_timezone(&place).is_some() {
+            seen.insert(place.clone());
+            names.push(place);
+        }
+    }
+
+    names
+}
+
+// Words of a `tz` expression: zone names and the "to" keyword
+pub fn complete_expression(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy().to_lowercase();
+
+    std::iter::once("to".to_string())
+        .chain(zone_names())
+        .filter(|word| word.to_lowercase().starts_with(&current))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+// Zone names alone, for commands that take a list of zones
+pub fn complete_zone(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy().to_lowercase();
+
+    zone_names()
+        .into_iter()
+        .filter(|word| word.to_lowercase().starts_with(&current))
+        .map(CompletionCandidate::new)
+        .collect()
+}
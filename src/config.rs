@@ -0,0 +1,295 @@
+// User configuration, read from TIMECALC_CONFIG or
+// ~/.config/timecalc/config.toml (honouring XDG_CONFIG_HOME):
+//
+//   default_zone = "WIB"       # `tz 10:00 PST` converts to this zone
+//   output = "json"            # used when --output is not given
+//   time_format = "12h"        # or "24h"
+//   week_start = "sunday"      # weeks of "next friday", "end of week"
+//   prefer_regions = ["IN"]    # IST means India, not Ireland or Israel
+//   strict = false             # true refuses ambiguous abbreviations
+//   dst = "earliest"           # local times skipped or repeated by DST
+//   clock_zones = ["WIB", "JST", "New York"]   # what `timecalc now` shows
+//   military = false           # true prints times as 0400Z
+//
+//   [aliases]
+//   office = "Asia/Jakarta"    # consulted before the built-in aliases
+//
+//   [[team]]                   # who `timecalc team` shows
+//   name = "Ana"
+//   zone = "WIB"
+//   hours = "08:00-16:00"      # default 09:00-17:00
+//   weekend = ["friday", "saturday"]   # default Saturday and Sunday
+//
+// A missing file is the same as an empty one. The loaded configuration is
+// installed once per thread, so parse_timezone can see the aliases without
+// every caller passing them around.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use chrono::Weekday;
+use serde::Deserialize;
+
+use crate::render::OutputFormat;
+use crate::{builtin_timezone, TimecalcError, WorkingHours, Zone, DEFAULT_CLOCK_ZONES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeFormat {
+    #[default]
+    H24,
+    H12,
+}
+
+impl TimeFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeFormat::H24 => "24h",
+            TimeFormat::H12 => "12h",
+        }
+    }
+
+    // strftime pattern for a time of day
+    pub fn pattern(self) -> &'static str {
+        match self {
+            TimeFormat::H24 => "%H:%M",
+            TimeFormat::H12 => "%I:%M %p",
+        }
+    }
+}
+
+// How to read a local time that a DST change skips (02:30 on a spring-forward
+// day) or repeats (01:30 on a fall-back day). Earliest and latest pick the
+// earlier or later of the two possible instants, shift-forward moves a skipped
+// time to the end of the gap, reject refuses both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum DstPolicy {
+    #[default]
+    Earliest,
+    Latest,
+    ShiftForward,
+    Reject,
+}
+
+impl DstPolicy {
+    pub fn name(self) -> &'static str {
+        match self {
+            DstPolicy::Earliest => "earliest",
+            DstPolicy::Latest => "latest",
+            DstPolicy::ShiftForward => "shift-forward",
+            DstPolicy::Reject => "reject",
+        }
+    }
+}
+
+// Someone on the `[[team]]` roster
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub name: String,
+    // As written, resolved by parse_timezone when the team is shown
+    pub zone: String,
+    pub hours: WorkingHours,
+    pub weekend: Vec<Weekday>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    // Where the configuration was looked for, and whether it was there
+    pub path: Option<PathBuf>,
+    pub loaded: bool,
+    pub default_zone: Option<String>,
+    pub output: OutputFormat,
+    pub time_format: TimeFormat,
+    pub week_start: Weekday,
+    // Country codes deciding what CST, IST, BST, ... mean
+    pub prefer_regions: Vec<String>,
+    pub strict: bool,
+    pub dst: DstPolicy,
+    pub clock_zones: Vec<String>,
+    // Times as military notation with the zone letter, "0400Z"
+    pub military: bool,
+    pub aliases: BTreeMap<String, Zone>,
+    pub team: Vec<Member>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: None,
+            loaded: false,
+            default_zone: None,
+            output: OutputFormat::Text,
+            time_format: TimeFormat::H24,
+            week_start: Weekday::Mon,
+            prefer_regions: Vec::new(),
+            strict: false,
+            dst: DstPolicy::Earliest,
+            clock_zones: DEFAULT_CLOCK_ZONES.iter().map(|zone| zone.to_string()).collect(),
+            military: false,
+            aliases: BTreeMap::new(),
+            team: Vec::new(),
+        }
+    }
+}
+
+// The file as written, validated into a Config by Config::parse
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    default_zone: Option<String>,
+    output: Option<String>,
+    time_format: Option<String>,
+    week_start: Option<String>,
+    prefer_regions: Vec<String>,
+    strict: bool,
+    dst: Option<String>,
+    clock_zones: Option<Vec<String>>,
+    military: bool,
+    aliases: BTreeMap<String, String>,
+    team: Vec<MemberFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemberFile {
+    name: String,
+    zone: String,
+    hours: Option<String>,
+    weekend: Option<Vec<String>>,
+}
+
+impl Config {
+    // TIMECALC_CONFIG, or timecalc/config.toml in the XDG config directory
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("TIMECALC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => dirs::home_dir()?.join(".config"),
+        };
+        Some(dir.join("timecalc").join("config.toml"))
+    }
+
+    pub fn load() -> Result<Config, TimecalcError> {
+        match Config::path() {
+            Some(path) => Config::load_from(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Config, TimecalcError> {
+        let invalid = |reason: String| TimecalcError::InvalidConfig {
+            path: path.display().to_string(),
+            reason,
+        };
+
+        let mut config = match fs::read_to_string(path) {
+            Ok(contents) => Config { loaded: true, ..Config::parse(&contents).map_err(invalid)? },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(err) => return Err(invalid(err.to_string())),
+        };
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    pub fn parse(contents: &str) -> Result<Config, String> {
+        let file: ConfigFile = toml::from_str(contents).map_err(|err| err.message().to_string())?;
+        let mut config = Config::default();
+
+        // Aliases point to an IANA name, a fixed offset or a built-in alias
+        for (alias, zone) in file.aliases {
+            let tz = builtin_timezone(&zone)
+                .ok_or_else(|| format!("alias '{}' points to unknown timezone '{}'", alias, zone))?;
+            config.aliases.insert(alias, tz);
+        }
+        if let Some(zone) = file.default_zone {
+            if !config.knows(&zone) {
+                return Err(format!("default_zone: unknown timezone '{}'", zone));
+            }
+            config.default_zone = Some(zone);
+        }
+        if let Some(zones) = file.clock_zones {
+            if let Some(zone) = zones.iter().find(|zone| !config.knows(zone)) {
+                return Err(format!("clock_zones: unknown timezone '{}'", zone));
+            }
+            config.clock_zones = zones;
+        }
+        for member in file.team {
+            if !config.knows(&member.zone) {
+                return Err(format!("team: unknown timezone '{}' for {}", member.zone, member.name));
+            }
+            let hours = match member.hours {
+                Some(hours) => WorkingHours::parse(&hours).ok_or_else(|| {
+                    format!("team: expected hours like '09:00-17:00' for {}, got '{}'", member.name, hours)
+                })?,
+                None => WorkingHours::default(),
+            };
+            let weekend = match member.weekend {
+                Some(days) => days
+                    .iter()
+                    .map(|day| day.parse().map_err(|_| format!("team: expected a weekday for {}, got '{}'", member.name, day)))
+                    .collect::<Result<_, _>>()?,
+                None => vec![Weekday::Sat, Weekday::Sun],
+            };
+            config.team.push(Member { name: member.name, zone: member.zone, hours, weekend });
+        }
+        if let Some(output) = file.output {
+            config.output = output.parse().map_err(|_| {
+                format!("output: expected 'text' or 'json', got '{}'", output)
+            })?;
+        }
+        if let Some(time_format) = file.time_format {
+            config.time_format = match time_format.to_lowercase().as_str() {
+                "24h" | "24" => TimeFormat::H24,
+                "12h" | "12" => TimeFormat::H12,
+                _ => return Err(format!("time_format: expected '12h' or '24h', got '{}'", time_format)),
+            };
+        }
+        if let Some(week_start) = file.week_start {
+            config.week_start = week_start
+                .parse()
+                .map_err(|_| format!("week_start: expected a weekday, got '{}'", week_start))?;
+        }
+
+        config.prefer_regions = file.prefer_regions.iter().map(|region| region.to_uppercase()).collect();
+        config.strict = file.strict;
+        config.military = file.military;
+        if let Some(dst) = file.dst {
+            config.dst = clap::ValueEnum::from_str(&dst, true).map_err(|_| {
+                format!("dst: expected 'earliest', 'latest', 'shift-forward' or 'reject', got '{}'", dst)
+            })?;
+        }
+
+        Ok(config)
+    }
+
+    // A zone name that one of the aliases or the built-in names resolve
+    fn knows(&self, zone: &str) -> bool {
+        self.alias(zone).or_else(|| builtin_timezone(zone)).is_some()
+    }
+
+    // User aliases match case-insensitively, like the built-in ones
+    pub fn alias(&self, name: &str) -> Option<Zone> {
+        self.aliases
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, tz)| *tz)
+    }
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Rc<Config>> = RefCell::new(Rc::new(Config::default()));
+}
+
+// Make `config` the one used by the library on this thread
+pub fn install(config: Config) {
+    ACTIVE.with(|active| *active.borrow_mut() = Rc::new(config));
+}
+
+pub fn active() -> Rc<Config> {
+    ACTIVE.with(|active| active.borrow().clone())
+}
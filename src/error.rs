@@ -0,0 +1,192 @@
+use std::fmt;
+
+// Every failure the library can report. The CLI prints these to stderr and
+// exits with `exit_code()`, one code per class so scripts can tell them apart:
+//
+//   2  usage error (unknown command, missing argument, bad 'month'/'year',
+//      unknown --output format, command not available in this context)
+//   3  number of days could not be parsed
+//   4  date/time could not be parsed
+//   5  timezone, city or country not known
+//   6  'to' keyword missing from a conversion
+//   7  invalid calendar date, or a date range that ends before it starts
+//   8  input file (or the REPL's terminal) could not be read
+//   9  one or more lines of a batch failed
+//  10  configuration file is invalid
+//  11  timezone name matches several zones (multi-zone country, or an
+//      ambiguous abbreviation in strict mode)
+//  12  local time skipped or repeated by a DST change, with --dst reject
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimecalcError {
+    MissingArgument { message: &'static str, example: &'static str },
+    InvalidPeriod(String),
+    InvalidDays(String),
+    InvalidDateTime(String),
+    UnsupportedTimezone(String),
+    MissingTo,
+    InvalidDate(String),
+    // A date that has to be written out, relative dates are not accepted
+    InvalidYearOrDate(String),
+    ReversedRange { from: String, to: String },
+    InvalidOutputFormat(String),
+    NotAvailable(&'static str),
+    ReadFailed { path: String, reason: String },
+    BatchFailed { failed: usize, total: usize },
+    InvalidConfig { path: String, reason: String },
+    UnknownPlace(String),
+    AmbiguousTimezone { name: String, candidates: Vec<String> },
+    DstTransition(String),
+}
+
+impl TimecalcError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TimecalcError::MissingArgument { .. }
+            | TimecalcError::InvalidPeriod(_)
+            | TimecalcError::InvalidOutputFormat(_)
+            | TimecalcError::NotAvailable(_) => 2,
+            TimecalcError::InvalidDays(_) => 3,
+            TimecalcError::InvalidDateTime(_) => 4,
+            TimecalcError::UnsupportedTimezone(_) | TimecalcError::UnknownPlace(_) => 5,
+            TimecalcError::MissingTo => 6,
+            TimecalcError::InvalidDate(_) | TimecalcError::InvalidYearOrDate(_) | TimecalcError::ReversedRange { .. } => 7,
+            TimecalcError::ReadFailed { .. } => 8,
+            TimecalcError::BatchFailed { .. } => 9,
+            TimecalcError::InvalidConfig { .. } => 10,
+            TimecalcError::AmbiguousTimezone { .. } => 11,
+            TimecalcError::DstTransition(_) => 12,
+        }
+    }
+
+    // Stable identifier used in JSON error output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TimecalcError::MissingArgument { .. } => "missing_argument",
+            TimecalcError::InvalidPeriod(_) => "invalid_period",
+            TimecalcError::InvalidDays(_) => "invalid_days",
+            TimecalcError::InvalidDateTime(_) => "invalid_datetime",
+            TimecalcError::UnsupportedTimezone(_) => "unsupported_timezone",
+            TimecalcError::MissingTo => "missing_to",
+            TimecalcError::InvalidDate(_) | TimecalcError::InvalidYearOrDate(_) => "invalid_date",
+            TimecalcError::ReversedRange { .. } => "reversed_range",
+            TimecalcError::InvalidOutputFormat(_) => "invalid_output_format",
+            TimecalcError::NotAvailable(_) => "not_available",
+            TimecalcError::ReadFailed { .. } => "read_failed",
+            TimecalcError::BatchFailed { .. } => "batch_failed",
+            TimecalcError::InvalidConfig { .. } => "invalid_config",
+            TimecalcError::UnknownPlace(_) => "unknown_place",
+            TimecalcError::AmbiguousTimezone { .. } => "ambiguous_timezone",
+            TimecalcError::DstTransition(_) => "dst_transition",
+        }
+    }
+
+    // Extra lines printed under the error message to help fix the input
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            TimecalcError::MissingArgument { example, .. } => Some(format!("Example: {}", example)),
+            TimecalcError::InvalidDateTime(_) => Some(
+                [
+                    "Examples:",
+                    "  timecalc tz 4:00 UTC+7 to WIB",
+                    "  timecalc tz 04:00AM UTC+8 to WIB",
+                    "  timecalc tz October 9, 2025 at 04:00AM UTC+8 to WIB",
+                    "  timecalc tz 2025-10-09 04:00 UTC+8 to WIB",
+                    "  timecalc tz now+3h WIB to UTC",
+                ]
+                .join("\n"),
+            ),
+            TimecalcError::UnsupportedTimezone(tz) => {
+                let mut hint = String::new();
+                let suggestions = crate::suggest_timezones(tz);
+                if !suggestions.is_empty() {
+                    hint.push_str(&format!("Did you mean: {}?\n", suggestions.join(", ")));
+                }
+                hint.push_str(
+                    "Supported: WIB, WITA, WIT, SGT, MYT, UTC, PST, EST, JST\n\
+                     Or a fixed offset: UTC+7, UTC+5:30, GMT-3, +0545, -03:30\n\
+                     Or any IANA name like Europe/Berlin, see 'timecalc zones'\n\
+                     Or a city or country: Tokyo, Bandung, New York, JP",
+                );
+                Some(hint)
+            }
+            TimecalcError::UnknownPlace(place) => {
+                let suggestions = crate::suggest_places(place);
+                if suggestions.is_empty() {
+                    Some("Try a larger city nearby, or a country like JP or Indonesia".to_string())
+                } else {
+                    Some(format!("Did you mean: {}?", suggestions.join(", ")))
+                }
+            }
+            TimecalcError::AmbiguousTimezone { candidates, .. } => Some(format!(
+                "Use one of: {}",
+                candidates.join(", ")
+            )),
+            TimecalcError::DstTransition(_) => Some(
+                "Pick a reading with --dst earliest, latest or shift-forward".to_string(),
+            ),
+            TimecalcError::InvalidDate(_) => Some(
+                "Or a day relative to today: today, tomorrow, yesterday, next friday,\n\
+                 last tuesday, this saturday, in 2 weeks, 3 days ago, end of month"
+                    .to_string(),
+            ),
+            TimecalcError::MissingTo => Some(
+                "Example: timecalc tz 4:00 UTC+7 to WIB\n\
+                 Or set default_zone in the config file ('timecalc config show')"
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimecalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimecalcError::MissingArgument { message, .. } => write!(f, "{}", message),
+            TimecalcError::InvalidPeriod(period) => {
+                write!(f, "Use 'month' or 'year' (got '{}')", period)
+            }
+            TimecalcError::InvalidDays(input) => write!(
+                f,
+                "Could not parse days: '{}'. Use format like: 69 days, 69d, or just 69",
+                input
+            ),
+            TimecalcError::InvalidDateTime(input) => {
+                write!(f, "Could not parse date/time: '{}'", input)
+            }
+            TimecalcError::UnsupportedTimezone(tz) => write!(f, "Unsupported timezone: '{}'", tz),
+            TimecalcError::MissingTo => write!(f, "Missing 'to' keyword"),
+            TimecalcError::InvalidDate(input) => {
+                write!(f, "Invalid date format: '{}'. Use YYYY-MM-DD", input)
+            }
+            TimecalcError::InvalidYearOrDate(input) => {
+                write!(f, "Invalid date format: '{}'. Use YYYY or YYYY-MM-DD", input)
+            }
+            TimecalcError::ReversedRange { from, to } => {
+                write!(f, "FROM must not be after TO ({} is after {})", from, to)
+            }
+            TimecalcError::InvalidOutputFormat(format) => {
+                write!(f, "Unknown output format: '{}'. Use 'text' or 'json'", format)
+            }
+            TimecalcError::NotAvailable(command) => {
+                write!(f, "'{}' cannot be used here", command)
+            }
+            TimecalcError::ReadFailed { path, reason } => {
+                write!(f, "Could not read '{}': {}", path, reason)
+            }
+            TimecalcError::BatchFailed { failed, total } => {
+                write!(f, "{} of {} lines failed", failed, total)
+            }
+            TimecalcError::InvalidConfig { path, reason } => {
+                write!(f, "Invalid configuration in '{}': {}", path, reason)
+            }
+            TimecalcError::UnknownPlace(place) => write!(f, "Unknown city or country: '{}'", place),
+            TimecalcError::AmbiguousTimezone { name, candidates } => {
+                write!(f, "'{}' matches {} timezones", name, candidates.len())
+            }
+            TimecalcError::DstTransition(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for TimecalcError {}
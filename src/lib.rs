@@ -1,189 +1,887 @@
-use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone};
-use chrono_tz::{Asia::Jakarta, America::Los_Angeles, Tz};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, MappedLocalTime, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::{Asia::Jakarta, America::Los_Angeles, Tz, TZ_VARIANTS};
+use regex::Regex;
+use std::sync::LazyLock;
 
-pub fn handle_future_date(args: &[String]) {
-    if args.is_empty() {
-        println!("ERROR: Please specify number of days");
-        println!("Example: timecalc future 69 days");
-        return;
+pub mod batch;
+pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod completions;
+pub mod repl;
+pub mod error;
+pub mod links;
+pub mod places;
+pub mod render;
+pub mod zone;
+
+pub use cli::print_help;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use config::{Config, DstPolicy};
+pub use error::TimecalcError;
+pub use zone::{Transition, Zone, ZoneOffset};
+
+// Result of `future`/`past`: the reference day and the day N days away
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateCalculation {
+    // The system zone "today" is in
+    pub zone: Zone,
+    pub today: DateTime<Local>,
+    pub days: i64,
+    pub direction: Direction,
+    pub date: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Future,
+    Past,
+}
+
+// Result of `convert`/`tz`, labels are the zone names as the user typed them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub from: DateTime<Zone>,
+    pub from_label: String,
+    pub to: DateTime<Zone>,
+    pub to_label: String,
+    // Ambiguous abbreviations that were resolved by picking one meaning
+    pub warnings: Vec<String>,
+    // How a local time skipped or repeated by a DST change was read
+    pub dst: Option<String>,
+    // The other destinations of `to WIB, JST, PST`, after the first in `to`
+    pub also: Vec<Destination>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Destination {
+    pub datetime: DateTime<Zone>,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Month,
+    Year,
+}
+
+// Result of `remaining`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemainingDays {
+    pub zone: Zone,
+    pub period: Period,
+    pub today: NaiveDate,
+    pub end: NaiveDate,
+    pub remaining: i64,
+    pub passed: u32,
+}
+
+// Result of `day`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayInfo {
+    pub date: NaiveDate,
+    pub weekday: Weekday,
+}
+
+// Result of `zones`: every matching IANA zone at the current instant
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneList {
+    pub search: Option<String>,
+    pub zones: Vec<DateTime<Tz>>,
+}
+
+// Result of `now`/`clock`: one instant in several zones
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldClock {
+    pub times: Vec<Destination>,
+    // Ambiguous abbreviations that were guessed
+    pub warnings: Vec<String>,
+}
+
+// Result of `zone`: how a zone is set up, as of now
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneInfo {
+    pub now: DateTime<Zone>,
+    pub standard: ZoneOffset,
+    // None for a zone that does not observe DST (any more)
+    pub dst: Option<ZoneOffset>,
+    pub previous: Option<Transition>,
+    pub next: Option<Transition>,
+    pub warnings: Vec<String>,
+}
+
+// Result of `transitions`: the offset changes of a zone between two dates
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionList {
+    pub zone: Zone,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub transitions: Vec<Transition>,
+    pub warnings: Vec<String>,
+}
+
+// What the local time of day means for reaching someone there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPeriod {
+    Working,
+    Daytime,
+    Night,
+}
+
+impl DayPeriod {
+    // Working hours are 09:00-17:00 on weekdays, daytime 06:00-18:00
+    pub fn of<T: TimeZone>(dt: &DateTime<T>) -> DayPeriod {
+        let weekday = !matches!(dt.weekday(), Weekday::Sat | Weekday::Sun);
+        match dt.hour() {
+            9..=16 if weekday => DayPeriod::Working,
+            6..=17 => DayPeriod::Daytime,
+            _ => DayPeriod::Night,
+        }
     }
 
-    let days = parse_days(args);
-    if days.is_none() {
-        println!("ERROR: Could not parse days. Use format like: 69 days, 69d, or just 69");
-        return;
+    pub fn name(self) -> &'static str {
+        match self {
+            DayPeriod::Working => "working hours",
+            DayPeriod::Daytime => "daytime",
+            DayPeriod::Night => "night",
+        }
     }
+}
 
-    let days = days.unwrap();
-    let today = Local::now();
-    let future_date = today + Duration::days(days);
+// A daily span of working time, "09:00-17:00". An end before the start runs
+// past midnight, "22:00-06:00".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
 
-    println!("\nDATE CALCULATION");
-    println!("=====================================");
-    println!("TODAY:        {} ({})",
-             today.format("%A, %B %d, %Y"),
-             today.format("%Y-%m-%d"));
-    println!("AFTER {} DAYS: {} ({})",
-             days,
-             future_date.format("%A, %B %d, %Y"),
-             future_date.format("%Y-%m-%d"));
-    println!("=====================================\n");
+impl Default for WorkingHours {
+    fn default() -> Self {
+        WorkingHours {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        }
+    }
 }
 
-pub fn handle_past_date(args: &[String]) {
-    if args.is_empty() {
-        println!("ERROR: Please specify number of days");
-        return;
+impl WorkingHours {
+    pub fn parse(input: &str) -> Option<WorkingHours> {
+        let (start, end) = input.split_once('-')?;
+        let time = |text: &str| NaiveTime::parse_from_str(text.trim(), "%H:%M").ok();
+        let hours = WorkingHours { start: time(start)?, end: time(end)? };
+        (hours.start != hours.end).then_some(hours)
     }
 
-    let days = parse_days(args);
-    if days.is_none() {
-        println!("ERROR: Could not parse days");
-        return;
+    // Local start and end of the shift that begins on `date`
+    pub fn span(&self, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let end_date = match self.end <= self.start {
+            true => date.succ_opt().unwrap_or(date),
+            false => date,
+        };
+        (date.and_time(self.start), end_date.and_time(self.end))
     }
+}
+
+impl std::fmt::Display for WorkingHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+    }
+}
 
-    let days = days.unwrap();
-    let today = Local::now();
-    let past_date = today - Duration::days(days);
+// Result of `team`: everyone on the roster at one instant
+#[derive(Debug, Clone, PartialEq)]
+pub struct Team {
+    pub members: Vec<MemberStatus>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberStatus {
+    pub name: String,
+    pub label: String,
+    pub hours: WorkingHours,
+    pub datetime: DateTime<Zone>,
+    // Today is one of their weekend days
+    pub day_off: bool,
+    pub working: bool,
+    // When they finish (while working) or next start, None if they never work
+    pub change: Option<DateTime<Zone>>,
+}
+
+// Result of `meet`: when everyone is inside working hours on one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeetingPlan {
+    // The day in the first participant's zone
+    pub date: NaiveDate,
+    pub hours: WorkingHours,
+    pub length: Duration,
+    pub participants: Vec<Participant>,
+    // Every stretch of that day everyone is working, in UTC
+    pub windows: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    // Best first
+    pub slots: Vec<MeetingSlot>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Participant {
+    pub zone: Zone,
+    pub label: String,
+}
 
-    println!("\nDATE CALCULATION");
-    println!("=====================================");
-    println!("TODAY:        {} ({})",
-             today.format("%A, %B %d, %Y"),
-             today.format("%Y-%m-%d"));
-    println!("{} DAYS AGO:  {} ({})",
-             days,
-             past_date.format("%A, %B %d, %Y"),
-             past_date.format("%Y-%m-%d"));
-    println!("=====================================\n");
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeetingSlot {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    // How far the slot stays from anyone's start or end of day
+    pub margin: Duration,
 }
 
-pub fn handle_timezone_convert(args: &[String]) {
-    if args.len() < 4 {
-        println!("ERROR: Invalid format");
-        println!("Example: timecalc convert 4:00 UTC+7 to WIB");
-        println!("         timecalc tz October 9, 2025 at 04:00AM UTC+8 to WIB");
-        return;
+// Result of `where`: a place and the current time in each of its zones
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub name: String,
+    pub times: Vec<DateTime<Tz>>,
+}
+
+pub fn handle_future_date(args: &[String], clock: &dyn Clock) -> Result<DateCalculation, TimecalcError> {
+    if args.is_empty() {
+        return Err(TimecalcError::MissingArgument {
+            message: "Please specify number of days",
+            example: "timecalc future 69 days",
+        });
     }
 
-    // Find "to" keyword position
-    let to_pos = args.iter().position(|s| s.to_lowercase() == "to");
-    if to_pos.is_none() {
-        println!("ERROR: Missing 'to' keyword");
-        return;
+    let days = parse_days(args).ok_or_else(|| TimecalcError::InvalidDays(args.join(" ")))?;
+    let today = clock.now();
+    let date = Duration::try_days(days)
+        .and_then(|offset| today.checked_add_signed(offset))
+        .ok_or_else(|| TimecalcError::InvalidDays(args.join(" ")))?;
+
+    Ok(DateCalculation {
+        zone: Zone::local(),
+        today,
+        days,
+        direction: Direction::Future,
+        date,
+    })
+}
+
+pub fn handle_past_date(args: &[String], clock: &dyn Clock) -> Result<DateCalculation, TimecalcError> {
+    if args.is_empty() {
+        return Err(TimecalcError::MissingArgument {
+            message: "Please specify number of days",
+            example: "timecalc past 30 days",
+        });
     }
-    let to_pos = to_pos.unwrap();
 
-    // Extract parts: everything before "to" is source, after is destination
-    let from_parts = &args[..to_pos];
-    let to_tz_str = &args[to_pos + 1];
+    let days = parse_days(args).ok_or_else(|| TimecalcError::InvalidDays(args.join(" ")))?;
+    let today = clock.now();
+    let date = Duration::try_days(days)
+        .and_then(|offset| today.checked_sub_signed(offset))
+        .ok_or_else(|| TimecalcError::InvalidDays(args.join(" ")))?;
+
+    Ok(DateCalculation {
+        zone: Zone::local(),
+        today,
+        days,
+        direction: Direction::Past,
+        date,
+    })
+}
+
+pub fn handle_timezone_convert(args: &[String], clock: &dyn Clock) -> Result<Conversion, TimecalcError> {
+    let args = &split_zone_suffix(args);
 
-    // Parse the from_parts to extract date, time, and timezone
-    let (naive_datetime, from_tz_str) = parse_datetime_and_tz(from_parts);
-    if naive_datetime.is_none() {
-        println!("ERROR: Could not parse date/time");
-        println!("Examples:");
-        println!("  timecalc tz 4:00 UTC+7 to WIB");
-        println!("  timecalc tz 04:00AM UTC+8 to WIB");
-        println!("  timecalc tz October 9, 2025 at 04:00AM UTC+8 to WIB");
-        println!("  timecalc tz 2025-10-09 04:00 UTC+8 to WIB");
-        return;
+    // Find "to" keyword position. Without one the configured default zone,
+    // if any, is the destination: `tz 10:00 PST`
+    let to_pos = args.iter().position(|s| s.to_lowercase() == "to");
+    let config = config::active();
+    let default_to = config.default_zone.as_ref().filter(|_| to_pos.is_none());
+
+    // "now" and "in 90 minutes" stand for a date and time, the zone may follow
+    let relative = args.first().is_some_and(|word| word.to_lowercase().starts_with("now") || word.eq_ignore_ascii_case("in"));
+    let minimum = if default_to.is_some() { 2 } else { 4 } - usize::from(relative);
+    if args.len() < minimum {
+        return Err(TimecalcError::MissingArgument {
+            message: "Invalid format",
+            example: "timecalc tz October 9, 2025 at 04:00AM UTC+8 to WIB",
+        });
     }
 
-    let naive_datetime = naive_datetime.unwrap();
+    // Extract parts: everything before "to" is source, after are the destinations
+    let (from_parts, to_tz_strs) = match (to_pos, default_to) {
+        (Some(to_pos), _) => {
+            let zones = split_zones(&args[to_pos + 1..]);
+            if zones.is_empty() {
+                return Err(TimecalcError::MissingArgument {
+                    message: "Missing destination timezone after 'to'",
+                    example: "timecalc tz 4:00 UTC+7 to WIB",
+                });
+            }
+            (&args[..to_pos], zones)
+        }
+        (None, Some(default_to)) => (&args[..], vec![default_to.clone()]),
+        (None, None) => return Err(TimecalcError::MissingTo),
+    };
+
+    // Parse the from_parts to extract date, time, and timezone. A relative
+    // source is an instant already, shown in the zone given or the local one.
+    let (source, from_tz_str) = match relative_source(from_parts) {
+        Some((offset, tz_str)) => {
+            let instant = clock
+                .now()
+                .checked_add_signed(offset)
+                .ok_or_else(|| TimecalcError::InvalidDateTime(from_parts.join(" ")))?;
+            (Err(instant), tz_str.unwrap_or_else(|| "local".to_string()))
+        }
+        None if relative && extract_time(&from_parts.join(" ").to_lowercase()).is_none() => {
+            return Err(TimecalcError::InvalidDateTime(from_parts.join(" ")));
+        }
+        None => {
+            let (naive_datetime, from_tz_str) = parse_datetime_and_tz(from_parts, clock);
+            let naive_datetime = naive_datetime.ok_or_else(|| {
+                let datetime_parts = from_parts.split_last().map_or(&[][..], |(_, rest)| rest);
+                TimecalcError::InvalidDateTime(datetime_parts.join(" "))
+            })?;
+            (Ok(naive_datetime), from_tz_str)
+        }
+    };
 
     // Parse timezones
-    let from_tz = parse_timezone(&from_tz_str);
-    let to_tz = parse_timezone(to_tz_str);
+    let from_tz = resolve_timezone(&from_tz_str)?;
+    let to_tzs = to_tz_strs
+        .iter()
+        .map(|tz_str| resolve_timezone(tz_str))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (from_dt, dst) = match source {
+        Ok(naive_datetime) => localize(from_tz, naive_datetime, config.dst)?,
+        Err(instant) => (instant.with_timezone(&from_tz), None),
+    };
+    let mut destinations = to_tz_strs.iter().zip(to_tzs).map(|(tz_str, tz)| Destination {
+        datetime: from_dt.with_timezone(&tz),
+        label: zone_label(tz_str, tz),
+    });
+    let first = destinations.next().expect("at least one destination");
+
+    Ok(Conversion {
+        from: from_dt,
+        from_label: zone_label(&from_tz_str, from_tz),
+        to: first.datetime,
+        to_label: first.label,
+        also: destinations.collect(),
+        warnings: std::iter::once(&from_tz_str)
+            .chain(&to_tz_strs)
+            .filter_map(|tz_str| abbreviation_warning(tz_str))
+            .collect(),
+        dst,
+    })
+}
+
+// "now", "now+3h", "now - 30 minutes" or "in 90 minutes", followed by the
+// zone to show the result in, if any: how far from the current instant the
+// source is, and that zone
+fn relative_source(parts: &[String]) -> Option<(Duration, Option<String>)> {
+    let re = Regex::new(r"(?i)^(?:now(?:\s*([+-])\s*(\d+\s*[a-z]+))?|in\s+(\d+\s*[a-z]+))(?:\s+(.+))?$").expect("valid regex");
+    let joined = parts.join(" ");
+    let caps = re.captures(joined.trim())?;
+    // "in 2 weeks 10:00 WIB" is a date, followed by a time
+    if caps.get(4).is_some_and(|zone| extract_time(&zone.as_str().to_lowercase()).is_some()) {
+        return None;
+    }
+
+    let offset = match caps.get(2).or(caps.get(3)) {
+        Some(amount) => parse_duration(amount.as_str())?,
+        None => Duration::zero(),
+    };
+    let offset = match caps.get(1).map(|sign| sign.as_str()) {
+        Some("-") => -offset,
+        _ => offset,
+    };
+    Some((offset, caps.get(4).map(|zone| zone.as_str().to_string())))
+}
+
+// The zones of a `to` clause: "WIB, JST, PST", "WIB JST PST" or
+// "New York, Tokyo". Words between commas that name one zone together stay
+// together, otherwise the longest run of up to three words naming a zone is
+// taken from the left.
+fn split_zones(words: &[String]) -> Vec<String> {
+    let names_zone = |name: &str| parse_timezone(name).is_some() || places::lookup(name).is_some();
+    let joined = words.join(" ");
+    let mut zones = Vec::new();
+
+    for group in joined.split(',').map(str::trim).filter(|group| !group.is_empty()) {
+        let group_words: Vec<&str> = group.split_whitespace().collect();
+        if names_zone(group) || group_words.iter().all(|word| !names_zone(word)) {
+            // One zone, or nothing recognisable to split on
+            zones.push(group.to_string());
+            continue;
+        }
 
-    if from_tz.is_none() || to_tz.is_none() {
-        println!("ERROR: Unsupported timezone");
-        println!("Supported: WIB, WITA, WIT, SGT, MYT, UTC, PST, EST, JST");
-        println!("Or use: UTC+7, UTC+8, UTC+9, UTC-7, UTC-8, etc.");
-        return;
+        let mut start = 0;
+        while start < group_words.len() {
+            let len = (2..=(group_words.len() - start).min(3))
+                .rev()
+                .find(|&n| names_zone(&group_words[start..start + n].join(" ")))
+                .unwrap_or(1);
+            zones.push(group_words[start..start + len].join(" "));
+            start += len;
+        }
     }
+    zones
+}
 
-    let from_tz = from_tz.unwrap();
-    let to_tz = to_tz.unwrap();
+// Place a local time in `zone`. A time that a DST change skips or repeats is
+// read according to `policy`, and the returned note says what happened.
+pub fn localize(
+    zone: Zone,
+    local: NaiveDateTime,
+    policy: DstPolicy,
+) -> Result<(DateTime<Zone>, Option<String>), TimecalcError> {
+    let time = config::active().time_format.pattern();
+    let (what, chosen) = match zone.from_local_datetime(&local) {
+        MappedLocalTime::Single(dt) => return Ok((dt, None)),
+        MappedLocalTime::Ambiguous(earliest, latest) => {
+            // Clocks reach `end` and go back to `end - (earliest - latest)`
+            let end = dst_boundary(zone, local);
+            let back = end - offset_difference(earliest.offset().fix(), latest.offset().fix());
+            let what = format!(
+                "{} happens twice in {} on {}, clocks go back from {} to {} ({} -> {})",
+                local.format(time),
+                zone.name(),
+                local.format("%Y-%m-%d"),
+                end.format(time),
+                back.format(time),
+                earliest.format("%Z"),
+                latest.format("%Z")
+            );
+            let chosen = match policy {
+                DstPolicy::Earliest | DstPolicy::ShiftForward => Some(("the first one, ", earliest)),
+                DstPolicy::Latest => Some(("the second one, ", latest)),
+                DstPolicy::Reject => None,
+            };
+            (what, chosen)
+        }
+        MappedLocalTime::None => {
+            // Clocks jump from `end - (after - before)` to `end`
+            let before = zone.from_utc_datetime(&(local - Duration::days(1)));
+            let after = zone.from_utc_datetime(&(local + Duration::days(1)));
+            let (before_offset, after_offset) = (before.offset().fix(), after.offset().fix());
+            let end = dst_boundary(zone, local);
+            let what = format!(
+                "{} does not exist in {} on {}, clocks skip from {} to {} ({} -> {})",
+                local.format(time),
+                zone.name(),
+                local.format("%Y-%m-%d"),
+                (end - offset_difference(after_offset, before_offset)).format(time),
+                end.format(time),
+                before.format("%Z"),
+                after.format("%Z")
+            );
+            // Read with the offset from after the change the time comes out
+            // earlier (01:30 PST), with the one from before it later (03:30 PDT)
+            let chosen = match policy {
+                DstPolicy::Earliest => Some(zone.from_utc_datetime(&(local - after_offset))),
+                DstPolicy::Latest => Some(zone.from_utc_datetime(&(local - before_offset))),
+                DstPolicy::ShiftForward => zone.from_local_datetime(&end).earliest(),
+                DstPolicy::Reject => None,
+            };
+            (what, chosen.map(|dt| ("", dt)))
+        }
+    };
 
-    let from_dt = from_tz.from_local_datetime(&naive_datetime).unwrap();
-    let to_dt = from_dt.with_timezone(&to_tz);
+    match chosen {
+        Some((which, dt)) => {
+            let note = format!(
+                "{}; using {}{} {} (--dst {})",
+                what,
+                which,
+                dt.format(time),
+                dt.format("%Z"),
+                policy.name()
+            );
+            Ok((dt, Some(note)))
+        }
+        None => Err(TimecalcError::DstTransition(what)),
+    }
+}
 
-    println!("\nTIMEZONE CONVERSION");
-    println!("=====================================");
-    println!("FROM: {} {} {}",
-             from_dt.format("%A, %B %d, %Y"),
-             from_dt.format("%H:%M"),
-             from_tz_str.to_uppercase());
-    println!("TO:   {} {} {}",
-             to_dt.format("%A, %B %d, %Y"),
-             to_dt.format("%H:%M"),
-             to_tz_str.to_uppercase());
-    println!("=====================================\n");
+fn offset_difference(a: FixedOffset, b: FixedOffset) -> Duration {
+    Duration::seconds(i64::from(a.local_minus_utc() - b.local_minus_utc()))
 }
 
-pub fn handle_remaining(args: &[String]) {
+// The first local minute at or after `local` that is neither skipped nor
+// repeated, i.e. where the gap or overlap around `local` ends
+fn dst_boundary(zone: Zone, local: NaiveDateTime) -> NaiveDateTime {
+    let start = local.date().and_hms_opt(local.hour(), local.minute(), 0).unwrap_or(local);
+    // No DST change skips or repeats more than a day
+    (0..=24 * 60)
+        .map(|minutes| start + Duration::minutes(minutes))
+        .find(|t| matches!(zone.from_local_datetime(t), MappedLocalTime::Single(_)))
+        .unwrap_or(local)
+}
+
+pub fn handle_remaining(args: &[String], clock: &dyn Clock) -> Result<RemainingDays, TimecalcError> {
     if args.is_empty() {
-        println!("ERROR: Specify 'month' or 'year'");
-        return;
+        return Err(TimecalcError::MissingArgument {
+            message: "Specify 'month' or 'year'",
+            example: "timecalc remaining month",
+        });
     }
 
     let period = args[0].to_lowercase();
-    let today = Local::now();
+    let today = clock.today();
 
     match period.as_str() {
         "month" => {
             let last_day = get_last_day_of_month(today.year(), today.month());
-            let remaining = last_day.signed_duration_since(today.date_naive()).num_days();
-
-            println!("\nDAYS REMAINING");
-            println!("=====================================");
-            println!("TODAY:           {}", today.format("%B %d, %Y"));
-            println!("END OF MONTH:    {}", last_day.format("%B %d, %Y"));
-            println!("DAYS REMAINING:  {} days", remaining);
-            println!("DAYS PASSED:     {} days", today.day());
-            println!("=====================================\n");
+
+            Ok(RemainingDays {
+                zone: Zone::local(),
+                period: Period::Month,
+                today,
+                end: last_day,
+                remaining: last_day.signed_duration_since(today).num_days(),
+                passed: today.day(),
+            })
         }
         "year" => {
             let last_day_of_year = NaiveDate::from_ymd_opt(today.year(), 12, 31).unwrap();
-            let remaining = last_day_of_year.signed_duration_since(today.date_naive()).num_days();
-            let day_of_year = today.ordinal();
-
-            println!("\nDAYS REMAINING");
-            println!("=====================================");
-            println!("TODAY:           {}", today.format("%B %d, %Y"));
-            println!("END OF YEAR:     December 31, {}", today.year());
-            println!("DAYS REMAINING:  {} days", remaining);
-            println!("DAYS PASSED:     {} days", day_of_year);
-            println!("=====================================\n");
+
+            Ok(RemainingDays {
+                zone: Zone::local(),
+                period: Period::Year,
+                today,
+                end: last_day_of_year,
+                remaining: last_day_of_year.signed_duration_since(today).num_days(),
+                passed: today.ordinal(),
+            })
         }
-        _ => println!("ERROR: Use 'month' or 'year'"),
+        _ => Err(TimecalcError::InvalidPeriod(args[0].clone())),
     }
 }
 
-pub fn handle_day_of_week(args: &[String]) {
+pub fn handle_day_of_week(args: &[String], clock: &dyn Clock) -> Result<DayInfo, TimecalcError> {
     if args.is_empty() {
-        println!("ERROR: Please provide a date");
-        println!("Example: timecalc day 2025-12-25");
-        return;
+        return Err(TimecalcError::MissingArgument {
+            message: "Please provide a date",
+            example: "timecalc day 2025-12-25",
+        });
     }
 
-    let date_str = &args[0];
-    let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d");
+    let date_str = args.join(" ");
+    let date = parse_date(&date_str, clock).ok_or(TimecalcError::InvalidDate(date_str))?;
+
+    Ok(DayInfo {
+        date,
+        weekday: date.weekday(),
+    })
+}
+
+pub fn handle_zones(search: Option<&str>, clock: &dyn Clock) -> ZoneList {
+    let now = clock.now();
+    let wanted = search.map(|search| search.trim().replace(' ', "_").to_lowercase());
 
-    if date.is_err() {
-        println!("ERROR: Invalid date format. Use YYYY-MM-DD");
-        return;
+    let zones = TZ_VARIANTS
+        .iter()
+        .filter(|tz| wanted.as_ref().is_none_or(|wanted| tz.name().to_lowercase().contains(wanted)))
+        .map(|tz| now.with_timezone(tz))
+        .collect();
+
+    ZoneList {
+        search: search.map(str::to_string),
+        zones,
     }
+}
+
+// The zones listed when `now` is given none and the config sets no clock_zones
+pub const DEFAULT_CLOCK_ZONES: &[&str] = &["WIB", "WITA", "WIT", "UTC"];
+
+pub fn handle_now(zones: &[String], at: Option<&str>, clock: &dyn Clock) -> Result<WorldClock, TimecalcError> {
+    let (instant, at_zone) = match at {
+        Some(at) => parse_instant(at, clock)?,
+        None => (clock.now().with_timezone(&Zone::Named(Tz::UTC)), None),
+    };
 
-    let date = date.unwrap();
-    let weekday = date.format("%A");
+    let zones = match split_zones(zones) {
+        zones if zones.is_empty() => config::active().clock_zones.clone(),
+        zones => zones,
+    };
 
-    println!("\nDAY OF WEEK");
-    println!("=====================================");
-    println!("DATE: {}", date.format("%B %d, %Y"));
-    println!("DAY:  {}", weekday);
-    println!("=====================================\n");
+    let times = zones
+        .iter()
+        .map(|tz_str| {
+            let tz = resolve_timezone(tz_str)?;
+            Ok(Destination {
+                datetime: instant.with_timezone(&tz),
+                label: zone_label(tz_str, tz),
+            })
+        })
+        .collect::<Result<_, TimecalcError>>()?;
+
+    Ok(WorldClock { times, warnings: abbreviation_warnings(at_zone.iter().chain(&zones)) })
+}
+
+pub fn handle_team(at: Option<&str>, clock: &dyn Clock) -> Result<Team, TimecalcError> {
+    let config = config::active();
+    if config.team.is_empty() {
+        return Err(TimecalcError::MissingArgument {
+            message: "No [[team]] entries in the config file",
+            example: "name = \"Ana\" and zone = \"WIB\" under [[team]], see 'timecalc help team'",
+        });
+    }
+
+    let (instant, at_zone) = match at {
+        Some(at) => parse_instant(at, clock)?,
+        None => (clock.now().with_timezone(&Zone::Named(Tz::UTC)), None),
+    };
+
+    let members = config
+        .team
+        .iter()
+        .map(|member| {
+            let tz = resolve_timezone(&member.zone)?;
+            let datetime = instant.with_timezone(&tz);
+            let (working, change) = shift_status(&datetime, member.hours, &member.weekend);
+            Ok(MemberStatus {
+                name: member.name.clone(),
+                label: zone_label(&member.zone, tz),
+                hours: member.hours,
+                day_off: member.weekend.contains(&datetime.weekday()),
+                datetime,
+                working,
+                change,
+            })
+        })
+        .collect::<Result<_, TimecalcError>>()?;
+
+    Ok(Team {
+        members,
+        warnings: abbreviation_warnings(at_zone.iter().chain(config.team.iter().map(|member| &member.zone))),
+    })
+}
+
+// Whether `now` falls in a shift, and when that shift ends or the next one
+// starts. Shifts begin on every day but the weekend ones; yesterday's is
+// included for hours running past midnight.
+fn shift_status(now: &DateTime<Zone>, hours: WorkingHours, weekend: &[Weekday]) -> (bool, Option<DateTime<Zone>>) {
+    let zone = now.timezone();
+    let local = |naive| localize(zone, naive, DstPolicy::ShiftForward).ok().map(|(dt, _)| dt);
+    let today = now.date_naive();
+
+    let shifts = (-1..=7)
+        .filter_map(|days| today.checked_add_signed(Duration::days(days)))
+        .filter(|date| !weekend.contains(&date.weekday()))
+        .filter_map(|date| {
+            let (start, end) = hours.span(date);
+            local(start).zip(local(end))
+        });
+    for (start, end) in shifts {
+        if *now < start {
+            return (false, Some(start));
+        }
+        if *now < end {
+            return (true, Some(end));
+        }
+    }
+    (false, None)
+}
+
+// Candidate meetings start every half hour from the beginning of a window
+const SLOT_STEP_MINUTES: i64 = 30;
+
+pub fn handle_meet(
+    zones: &[String],
+    date: Option<&str>,
+    hours: WorkingHours,
+    length: Duration,
+    clock: &dyn Clock,
+) -> Result<MeetingPlan, TimecalcError> {
+    let zones = split_zones(zones);
+    let participants = zones
+        .iter()
+        .map(|tz_str| {
+            let zone = resolve_timezone(tz_str)?;
+            Ok(Participant { zone, label: zone_label(tz_str, zone) })
+        })
+        .collect::<Result<Vec<_>, TimecalcError>>()?;
+    let Some(first) = participants.first().map(|participant| participant.zone) else {
+        return Err(TimecalcError::MissingArgument {
+            message: "Please name the zones of the participants",
+            example: "timecalc meet WIB JST PST --date 2025-10-20",
+        });
+    };
+
+    let date = match date {
+        Some(date) => parse_date(date, clock).ok_or_else(|| TimecalcError::InvalidDate(date.to_string()))?,
+        None => clock.now().with_timezone(&first).date_naive(),
+    };
+    let local = |zone: Zone, naive: NaiveDateTime| {
+        localize(zone, naive, DstPolicy::ShiftForward).map(|(dt, _)| dt.with_timezone(&Utc))
+    };
+
+    // Everyone's shifts that can touch the day, narrowed down one person at a time
+    let day_end = date.succ_opt().ok_or_else(|| TimecalcError::InvalidDate(date.to_string()))?;
+    let mut windows = vec![(local(first, date.and_time(NaiveTime::MIN))?, local(first, day_end.and_time(NaiveTime::MIN))?)];
+    let mut shifts = Vec::new();
+    for participant in &participants {
+        let theirs = [date.pred_opt(), Some(date), Some(day_end)]
+            .into_iter()
+            .flatten()
+            .map(|day| {
+                let (start, end) = hours.span(day);
+                Ok((local(participant.zone, start)?, local(participant.zone, end)?))
+            })
+            .collect::<Result<Vec<_>, TimecalcError>>()?;
+        windows = windows
+            .iter()
+            .flat_map(|&(start, end)| theirs.iter().map(move |&(from, to)| (start.max(from), end.min(to))))
+            .filter(|(start, end)| start < end)
+            .collect();
+        shifts.push(theirs);
+    }
+
+    let mut slots = Vec::new();
+    for &(window_start, window_end) in &windows {
+        let mut start = window_start;
+        while start + length <= window_end {
+            let end = start + length;
+            let margin = shifts
+                .iter()
+                .filter_map(|theirs| {
+                    theirs
+                        .iter()
+                        .find(|(from, to)| *from <= start && end <= *to)
+                        .map(|(from, to)| (start - *from).min(*to - end))
+                })
+                .min()
+                .unwrap_or_default();
+            slots.push(MeetingSlot { start, end, margin });
+            start += Duration::minutes(SLOT_STEP_MINUTES);
+        }
+    }
+    slots.sort_by(|a, b| b.margin.cmp(&a.margin).then(a.start.cmp(&b.start)));
+
+    Ok(MeetingPlan { date, hours, length, participants, windows, slots, warnings: abbreviation_warnings(&zones) })
+}
+
+// An instant given as --now accepts it ("2025-10-09 09:00", RFC 3339), or as
+// a date and time in a zone like a conversion source ("2025-10-09 09:00 WIB").
+// The zone name comes back too, so it can be warned about.
+fn parse_instant(input: &str, clock: &dyn Clock) -> Result<(DateTime<Zone>, Option<String>), TimecalcError> {
+    if let Some(fixed) = FixedClock::parse(input) {
+        return Ok((fixed.now().with_timezone(&Zone::Named(Tz::UTC)), None));
+    }
+
+    let words: Vec<String> = input.split_whitespace().map(str::to_string).collect();
+    let (naive_datetime, tz_str) = parse_datetime_and_tz(&words, clock);
+    let naive_datetime = naive_datetime.ok_or_else(|| TimecalcError::InvalidDateTime(input.to_string()))?;
+    let tz = resolve_timezone(&tz_str)?;
+    let (dt, _) = localize(tz, naive_datetime, config::active().dst)?;
+    Ok((dt, Some(tz_str)))
+}
+
+pub fn handle_zone_info(args: &[String], clock: &dyn Clock) -> Result<ZoneInfo, TimecalcError> {
+    if args.is_empty() {
+        return Err(TimecalcError::MissingArgument {
+            message: "Please name a timezone",
+            example: "timecalc zone America/New_York",
+        });
+    }
+
+    let name = args.join(" ");
+    let zone = resolve_timezone(&name)?.canonical();
+    let now = clock.now().with_timezone(&zone);
+    let instant = now.with_timezone(&chrono::Utc);
+    let previous = zone.previous_transition(instant);
+    let next = zone.next_transition(instant);
+
+    // Standard and DST offsets from around now; a DST change more than a
+    // year away (or ago) means the zone does not observe DST
+    let recent = |transition: &Transition| (transition.at - instant).num_days().abs() <= 366;
+    let offsets: Vec<ZoneOffset> = std::iter::once(*now.offset())
+        .chain(previous.iter().chain(&next).filter(|t| recent(t)).flat_map(|t| [t.before, t.after]))
+        .collect();
+
+    Ok(ZoneInfo {
+        now,
+        standard: offsets.iter().find(|offset| !offset.is_dst()).copied().unwrap_or(*now.offset()),
+        dst: offsets.iter().find(|offset| offset.is_dst()).copied(),
+        previous,
+        next,
+        warnings: abbreviation_warnings([&name]),
+    })
+}
+
+// `transitions ZONE`, `transitions ZONE 2025`, `transitions ZONE 2020 2025`
+// or `transitions ZONE 2025-03-01 2025-11-30`, the range being inclusive
+pub fn handle_transitions(args: &[String], clock: &dyn Clock) -> Result<TransitionList, TimecalcError> {
+    // A bare year stands for its first day at the start of a range, its last at the end
+    let range_end = |word: &str, end: bool| -> Option<NaiveDate> {
+        match word.parse::<i32>() {
+            Ok(year) if word.len() == 4 => NaiveDate::from_ymd_opt(year, if end { 12 } else { 1 }, if end { 31 } else { 1 }),
+            _ => NaiveDate::parse_from_str(word, "%Y-%m-%d").ok(),
+        }
+    };
+    let is_year = |word: &str| word.len() == 4 && word.chars().all(|c| c.is_ascii_digit());
+    let is_date = |word: &str| is_year(word) || (word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('-'));
+
+    let this_year = clock.today().year().to_string();
+
+    let (zone_words, from, to) = match args {
+        [zone @ .., from, to] if !zone.is_empty() && is_date(from) && is_date(to) => (zone, from.as_str(), to.as_str()),
+        [zone @ .., year] if !zone.is_empty() && is_year(year) => (zone, year.as_str(), year.as_str()),
+        [zone @ .., last] if !zone.is_empty() && is_date(last) => {
+            return Err(TimecalcError::InvalidYearOrDate(last.clone()));
+        }
+        _ => (args, this_year.as_str(), this_year.as_str()),
+    };
+    if zone_words.is_empty() {
+        return Err(TimecalcError::MissingArgument {
+            message: "Please name a timezone",
+            example: "timecalc transitions America/New_York 2025",
+        });
+    }
+    let from = range_end(from, false).ok_or_else(|| TimecalcError::InvalidYearOrDate(from.to_string()))?;
+    let to = range_end(to, true).ok_or_else(|| TimecalcError::InvalidYearOrDate(to.to_string()))?;
+    if from > to {
+        return Err(TimecalcError::ReversedRange { from: from.to_string(), to: to.to_string() });
+    }
+
+    let name = zone_words.join(" ");
+    let zone = resolve_timezone(&name)?;
+    let utc = |date: NaiveDate| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+
+    Ok(TransitionList {
+        zone,
+        from,
+        to,
+        transitions: zone.transitions(utc(from), utc(to + Duration::days(1))),
+        warnings: abbreviation_warnings([&name]),
+    })
+}
+
+pub fn handle_where(args: &[String], clock: &dyn Clock) -> Result<Location, TimecalcError> {
+    if args.is_empty() {
+        return Err(TimecalcError::MissingArgument {
+            message: "Please name a city or country",
+            example: "timecalc where Bandung",
+        });
+    }
+
+    let query = args.join(" ");
+    let place = places::lookup(&query).ok_or(TimecalcError::UnknownPlace(query))?;
+    let now = clock.now();
+
+    Ok(Location {
+        name: place.name,
+        times: place.zones.iter().map(|tz| now.with_timezone(tz)).collect(),
+    })
 }
 
 pub fn parse_days(args: &[String]) -> Option<i64> {
@@ -199,46 +897,395 @@ pub fn parse_days(args: &[String]) -> Option<i64> {
     num_str.parse::<i64>().ok()
 }
 
-pub fn parse_timezone(tz_str: &str) -> Option<Tz> {
-    let tz_upper = tz_str.to_uppercase();
+// Durations like "3d", "2h", "90m", "1w", "45s" or "90 minutes"
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    static RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(\d+)\s*(w|weeks?|d|days?|h|hrs?|hours?|m|mins?|minutes?|s|secs?|seconds?)$").expect("valid regex")
+    });
+    let input = input.trim().to_lowercase();
+    let caps = RE.captures(&input)?;
+    let amount: i64 = caps.get(1)?.as_str().parse().ok()?;
 
-    match tz_upper.as_str() {
-        // Indonesia
-        "WIB" | "UTC+7" => Some(Jakarta),                    // Western Indonesia (Jakarta, Sumatra)
-        "WITA" | "UTC+8" => Some(Tz::Asia__Makassar),       // Central Indonesia (Bali, Sulawesi)
-        "WIT" | "UTC+9" => Some(Tz::Asia__Jayapura),        // Eastern Indonesia (Papua, Maluku)
-        
-        // Southeast Asia
-        "SGT" => Some(Tz::Asia__Singapore),                  // Singapore
-        "MYT" => Some(Tz::Asia__Kuala_Lumpur),              // Malaysia
-        
-        // Common timezones
-        "UTC" | "UTC+0" => Some(Tz::UTC),
-        "PST" | "UTC-8" => Some(Los_Angeles),
-        "EST" | "UTC-5" => Some(Tz::EST5EDT),
-        "JST" => Some(Tz::Japan),                            // Japan
-        "UTC-7" => Some(Tz::MST7MDT),
-        
-        _ => None,
+    match caps.get(2)?.as_str().chars().next()? {
+        'w' => Duration::try_weeks(amount),
+        'd' => Duration::try_days(amount),
+        'h' => Duration::try_hours(amount),
+        'm' => Duration::try_minutes(amount),
+        _ => Duration::try_seconds(amount),
     }
 }
 
-pub fn parse_datetime_and_tz(parts: &[String]) -> (Option<chrono::NaiveDateTime>, String) {
+// Zone names parse_timezone understands, matched case-insensitively
+pub const TIMEZONE_ALIASES: &[(&str, Tz)] = &[
+    // Indonesia
+    ("WIB", Jakarta),                   // Western Indonesia (Jakarta, Sumatra)
+    ("WITA", Tz::Asia__Makassar),       // Central Indonesia (Bali, Sulawesi)
+    ("WIT", Tz::Asia__Jayapura),        // Eastern Indonesia (Papua, Maluku)
+
+    // Southeast Asia
+    ("SGT", Tz::Asia__Singapore),       // Singapore
+    ("MYT", Tz::Asia__Singapore),       // Malaysia, one zone with Singapore
+
+    // Common timezones
+    ("UTC", Tz::Etc__UTC),
+    ("PST", Los_Angeles),
+    ("EST", Tz::America__New_York),
+    ("JST", Tz::Asia__Tokyo),            // Japan
+
+    // North America, standard and daylight names map to the same zone
+    ("PDT", Los_Angeles),
+    ("MDT", Tz::America__Denver),
+    ("EDT", Tz::America__New_York),
+    ("AKST", Tz::America__Anchorage),
+    ("AKDT", Tz::America__Anchorage),
+    ("HST", Tz::Pacific__Honolulu),
+    ("ADT", Tz::America__Halifax),
+    ("NST", Tz::America__St_Johns),
+    ("NDT", Tz::America__St_Johns),
+
+    // Europe and Africa
+    ("WEST", Tz::Europe__Lisbon),
+    ("CEST", Tz::Europe__Berlin),
+    ("EEST", Tz::Europe__Athens),
+    ("MSK", Tz::Europe__Moscow),
+    ("WAT", Tz::Africa__Lagos),
+    ("CAT", Tz::Africa__Maputo),
+    ("EAT", Tz::Africa__Nairobi),
+    ("SAST", Tz::Africa__Johannesburg),
+
+    // Asia and Oceania
+    ("PKT", Tz::Asia__Karachi),
+    ("NPT", Tz::Asia__Kathmandu),
+    ("ICT", Tz::Asia__Bangkok),
+    ("PHT", Tz::Asia__Manila),
+    ("HKT", Tz::Asia__Hong_Kong),
+    ("KST", Tz::Asia__Seoul),
+    ("AWST", Tz::Australia__Perth),
+    ("ACST", Tz::Australia__Adelaide),
+    ("ACDT", Tz::Australia__Adelaide),
+    ("AEST", Tz::Australia__Sydney),
+    ("AEDT", Tz::Australia__Sydney),
+    ("NZST", Tz::Pacific__Auckland),
+    ("NZDT", Tz::Pacific__Auckland),
+
+    // South America
+    ("ART", Tz::America__Argentina__Buenos_Aires),
+    ("BRT", Tz::America__Sao_Paulo),
+    ("CLT", Tz::America__Santiago),
+];
+// Offsets such as UTC+7 or -03:30 are not in the table, they are fixed
+// offsets (see Zone::parse_offset)
+
+// Military/nautical zones: a letter or its phonetic name, with the offset in
+// hours. J (Juliett) is the observer's local time. India and Lima name the
+// country and the city first, their letters I and L always work.
+pub const MILITARY_ZONES: &[(char, &str, i32)] = &[
+    ('A', "Alpha", 1), ('B', "Bravo", 2), ('C', "Charlie", 3), ('D', "Delta", 4),
+    ('E', "Echo", 5), ('F', "Foxtrot", 6), ('G', "Golf", 7), ('H', "Hotel", 8),
+    ('I', "India", 9), ('K', "Kilo", 10), ('L', "Lima", 11), ('M', "Mike", 12),
+    ('N', "November", -1), ('O', "Oscar", -2), ('P', "Papa", -3), ('Q', "Quebec", -4),
+    ('R', "Romeo", -5), ('S', "Sierra", -6), ('T', "Tango", -7), ('U', "Uniform", -8),
+    ('V', "Victor", -9), ('W', "Whiskey", -10), ('X', "X-ray", -11), ('Y', "Yankee", -12),
+    ('Z', "Zulu", 0),
+];
+
+// "Z", "zulu", "R", "Romeo", "J"/"Juliett" for local time. Single letters
+// must be upper case (except z), so a stray "a" is not read as Alpha.
+pub fn military_zone(tz_str: &str) -> Option<Zone> {
+    let tz_str = tz_str.trim();
+    if tz_str == "J" || ["Juliet", "Juliett"].iter().any(|name| name.eq_ignore_ascii_case(tz_str)) {
+        return Some(Zone::local());
+    }
+    let (_, _, hours) = MILITARY_ZONES.iter().find(|(letter, name, _)| {
+        tz_str.eq_ignore_ascii_case(name) || tz_str == letter.to_string() || (tz_str == "z" && *letter == 'Z')
+    })?;
+    FixedOffset::east_opt(hours * 3600).map(Zone::Fixed)
+}
+
+// The letter for a whole-hour offset from UTC-12 to UTC+12
+pub fn military_letter(offset: FixedOffset) -> Option<char> {
+    let seconds = offset.local_minus_utc();
+    if seconds % 3600 != 0 {
+        return None;
+    }
+    MILITARY_ZONES
+        .iter()
+        .find(|(_, _, hours)| hours * 3600 == seconds)
+        .map(|(letter, _, _)| *letter)
+}
+
+// Abbreviations that mean different zones in different places, as
+// (country code, zone, description). The first one is used unless the
+// config's prefer_regions lists the country of another.
+pub type Meaning = (&'static str, Tz, &'static str);
+
+pub const AMBIGUOUS_ABBREVIATIONS: &[(&str, &[Meaning])] = &[
+    ("CST", &[
+        ("US", Tz::America__Chicago, "US Central"),
+        ("CN", Tz::Asia__Shanghai, "China"),
+        ("CU", Tz::America__Havana, "Cuba"),
+    ]),
+    ("CDT", &[
+        ("US", Tz::America__Chicago, "US Central"),
+        ("CU", Tz::America__Havana, "Cuba"),
+    ]),
+    ("IST", &[
+        ("IN", Tz::Asia__Kolkata, "India"),
+        ("IE", Tz::Europe__Dublin, "Ireland"),
+        ("IL", Tz::Asia__Jerusalem, "Israel"),
+    ]),
+    ("BST", &[
+        ("GB", Tz::Europe__London, "British Summer"),
+        ("BD", Tz::Asia__Dhaka, "Bangladesh"),
+    ]),
+    ("AST", &[
+        ("CA", Tz::America__Halifax, "Atlantic"),
+        ("SA", Tz::Asia__Riyadh, "Arabia"),
+    ]),
+    ("GST", &[
+        ("AE", Tz::Asia__Dubai, "Gulf"),
+        ("GS", Tz::Atlantic__South_Georgia, "South Georgia"),
+    ]),
+    ("MST", &[
+        ("US", Tz::America__Denver, "US Mountain"),
+        ("MY", Tz::Asia__Kuala_Lumpur, "Malaysia"),
+    ]),
+    ("SST", &[
+        ("AS", Tz::Pacific__Pago_Pago, "Samoa"),
+        ("SG", Tz::Asia__Singapore, "Singapore"),
+    ]),
+    ("AMT", &[
+        ("BR", Tz::America__Manaus, "Amazon"),
+        ("AM", Tz::Asia__Yerevan, "Armenia"),
+    ]),
+];
+
+// User aliases from the config file win over the built-in table
+pub fn parse_timezone(tz_str: &str) -> Option<Zone> {
+    config::active().alias(tz_str).or_else(|| builtin_timezone(tz_str))
+}
+
+fn builtin_alias(tz_str: &str) -> Option<Tz> {
+    TIMEZONE_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(tz_str))
+        .map(|(_, tz)| *tz)
+}
+
+// The meanings of an ambiguous abbreviation, the one to use first
+pub fn abbreviation_meanings(tz_str: &str) -> Option<Vec<Meaning>> {
+    let (_, meanings) = AMBIGUOUS_ABBREVIATIONS
+        .iter()
+        .find(|(abbreviation, _)| abbreviation.eq_ignore_ascii_case(tz_str))?;
+
+    let config = config::active();
+    let rank = |region: &str| {
+        config
+            .prefer_regions
+            .iter()
+            .position(|preferred| preferred.eq_ignore_ascii_case(region))
+            .unwrap_or(usize::MAX)
+    };
+    let mut meanings = meanings.to_vec();
+    // Stable, so without a preference the table order stays
+    meanings.sort_by_key(|(region, _, _)| rank(region));
+    Some(meanings)
+}
+
+// "'CST' is ambiguous, using ..." for an abbreviation whose meaning had to
+// be guessed. None for anything else, including abbreviations settled by a
+// user alias or by prefer_regions.
+pub fn abbreviation_warning(tz_str: &str) -> Option<String> {
+    let config = config::active();
+    if config.alias(tz_str).is_some() {
+        return None;
+    }
+    let meanings = abbreviation_meanings(tz_str)?;
+    if config.prefer_regions.iter().any(|region| region.eq_ignore_ascii_case(meanings[0].0)) {
+        return None;
+    }
+    let describe = |(_, tz, description): &Meaning| format!("{} ({})", tz.name(), description);
+
+    Some(format!(
+        "'{}' is ambiguous, using {}; it can also mean {}",
+        tz_str,
+        describe(&meanings[0]),
+        meanings[1..].iter().map(describe).collect::<Vec<_>>().join(", ")
+    ))
+}
+
+// abbreviation_warning for each name, the same warning only once
+fn abbreviation_warnings<'a>(names: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for warning in names.into_iter().filter_map(|name| abbreviation_warning(name)) {
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+    warnings
+}
+
+// Tried in order: local/here, built-in aliases, ambiguous abbreviations, fixed
+// offsets, any IANA name, cities and countries with a single zone, military
+// letters. "asia/jakarta" and "America/Sao Paulo" work as well as
+// "America/Sao_Paulo"
+pub fn builtin_timezone(tz_str: &str) -> Option<Zone> {
+    let iana = tz_str.trim().replace(' ', "_");
+    if is_local_keyword(tz_str) {
+        return Some(Zone::local());
+    }
+
+    builtin_alias(tz_str)
+        .or_else(|| abbreviation_meanings(tz_str).map(|meanings| meanings[0].1))
+        .map(Zone::Named)
+        .or_else(|| Zone::parse_offset(tz_str))
+        .or_else(|| {
+            TZ_VARIANTS
+                .iter()
+                .find(|tz| tz.name().eq_ignore_ascii_case(&iana))
+                .map(|tz| Zone::Named(*tz))
+        })
+        .or_else(|| match places::lookup(tz_str) {
+            Some(place) if place.zones.len() == 1 => Some(Zone::Named(place.zones[0])),
+            _ => None,
+        })
+        .or_else(|| military_zone(tz_str))
+}
+
+// parse_timezone, but tells an unknown name apart from a country that spans
+// several zones, and in strict mode refuses ambiguous abbreviations
+pub fn resolve_timezone(tz_str: &str) -> Result<Zone, TimecalcError> {
+    if config::active().strict && abbreviation_warning(tz_str).is_some() {
+        let meanings = abbreviation_meanings(tz_str).unwrap_or_default();
+        return Err(TimecalcError::AmbiguousTimezone {
+            name: tz_str.to_string(),
+            candidates: meanings
+                .iter()
+                .map(|(_, tz, description)| format!("{} ({})", tz.name(), description))
+                .collect(),
+        });
+    }
+    if let Some(zone) = parse_timezone(tz_str) {
+        return Ok(zone);
+    }
+
+    match places::lookup(tz_str) {
+        Some(place) => Err(TimecalcError::AmbiguousTimezone {
+            name: tz_str.to_string(),
+            candidates: place.zones.iter().map(|tz| tz.name().to_string()).collect(),
+        }),
+        None => Err(TimecalcError::UnsupportedTimezone(tz_str.to_string())),
+    }
+}
+
+// `local` and `here` stand for the system's zone: `tz 09:00 JST to local`
+pub const LOCAL_KEYWORDS: &[&str] = &["local", "here"];
+
+pub fn is_local_keyword(tz_str: &str) -> bool {
+    LOCAL_KEYWORDS.iter().any(|keyword| keyword.eq_ignore_ascii_case(tz_str.trim()))
+}
+
+// Aliases and offsets are shown upper-cased as typed ("wib" -> "WIB"), IANA
+// names and places in their canonical spelling ("america/sao paulo" ->
+// "America/Sao_Paulo", "new york" -> "New York")
+pub fn zone_label(tz_str: &str, zone: Zone) -> String {
+    match zone {
+        _ if is_local_keyword(tz_str) && config::active().alias(tz_str).is_none() => zone.name(),
+        Zone::Named(tz) if tz.name().eq_ignore_ascii_case(&tz_str.trim().replace(' ', "_")) => {
+            tz.name().to_string()
+        }
+        Zone::Named(tz) if builtin_alias(tz_str).is_none() && config::active().alias(tz_str).is_none() => {
+            match places::lookup(tz_str) {
+                Some(place) if place.zones == [tz] => place.name,
+                _ => tz_str.to_uppercase(),
+            }
+        }
+        _ => tz_str.to_uppercase(),
+    }
+}
+
+// Up to three known zone names closest to a name parse_timezone rejected
+pub fn suggest_timezones(tz_str: &str) -> Vec<String> {
+    closest_names(tz_str, completions::zone_names())
+}
+
+// Same for a city or country `where` did not find
+pub fn suggest_places(place: &str) -> Vec<String> {
+    closest_names(place, places::names())
+}
+
+fn closest_names(input: &str, candidates: Vec<String>) -> Vec<String> {
+    let wanted = input.trim().replace(' ', "_").to_lowercase();
+    let mut scored: Vec<(f64, String)> = candidates
+        .into_iter()
+        .map(|name| {
+            let lower = name.replace(' ', "_").to_lowercase();
+            // "berln" should find Europe/Berlin, so also score the city part
+            let city = lower.rsplit('/').next().unwrap_or(&lower);
+            let score = strsim::jaro_winkler(&wanted, &lower).max(strsim::jaro_winkler(&wanted, city));
+            (score, name)
+        })
+        .filter(|(score, _)| *score >= 0.85)
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().take(3).map(|(_, name)| name).collect()
+}
+
+pub fn parse_datetime_and_tz(parts: &[String], clock: &dyn Clock) -> (Option<chrono::NaiveDateTime>, String) {
     // Try to find timezone at the end (last token before "to")
     if parts.is_empty() {
         return (None, String::new());
     }
+    let parts = &split_zone_suffix(parts);
+
+    // Zone names may contain spaces ("America/Sao Paulo", "New York"), so take the
+    // longest tail of up to three words that names a zone
+    let tz_words = (2..=parts.len().min(3))
+        .rev()
+        .find(|&n| {
+            let name = parts[parts.len() - n..].join(" ");
+            parse_timezone(&name).is_some() || places::lookup(&name).is_some()
+        })
+        .unwrap_or(1);
+
+    let tz_str = parts[parts.len() - tz_words..].join(" ");
+    let mut datetime_str = parts[..parts.len() - tz_words].join(" ");
 
-    let tz_str = parts[parts.len() - 1].clone();
-    let datetime_str = parts[..parts.len() - 1].join(" ");
+    // Military times leave out the colon: "1600 Romeo", "0400 Z"
+    if military_zone(&tz_str).is_some() {
+        if let Some(caps) = Regex::new(r"(?:^|\s)(\d{2})(\d{2})$").ok().and_then(|re| re.captures(&datetime_str)) {
+            let colon_time = format!("{}:{}", &caps[1], &caps[2]);
+            datetime_str = format!("{}{}", &datetime_str[..caps.get(1).map_or(0, |m| m.start())], colon_time);
+        }
+    }
 
     // Try various date/time formats
-    let naive_dt = parse_flexible_datetime(&datetime_str);
+    let naive_dt = parse_flexible_datetime(&datetime_str, clock);
     
     (naive_dt, tz_str)
 }
 
-pub fn parse_flexible_datetime(input: &str) -> Option<chrono::NaiveDateTime> {
+// A zone letter glued onto a time, "0400Z", "16:00R" or ISO 8601
+// "2025-10-09T04:00Z", split off into a word of its own
+fn split_zone_suffix(parts: &[String]) -> Vec<String> {
+    let re = Regex::new(r"^(?:(\d{4}-\d{2}-\d{2})T)?(\d{2}:?\d{2})(?::\d{2})?([A-Z]|z)$").expect("valid regex");
+
+    parts
+        .iter()
+        .flat_map(|part| match re.captures(part) {
+            Some(caps) => caps
+                .get(1)
+                .map(|date| date.as_str().to_string())
+                .into_iter()
+                .chain([caps[2].to_string(), caps[3].to_uppercase()])
+                .collect(),
+            None => vec![part.clone()],
+        })
+        .collect()
+}
+
+pub fn parse_flexible_datetime(input: &str, clock: &dyn Clock) -> Option<chrono::NaiveDateTime> {
     let input_lower = input.to_lowercase();
 
     // Remove "at" keyword if present
@@ -248,13 +1295,12 @@ pub fn parse_flexible_datetime(input: &str) -> Option<chrono::NaiveDateTime> {
     let time = extract_time(&cleaned)?;
 
     // Extract date
-    let date = extract_date(&cleaned);
+    let date = extract_date(&cleaned, clock);
 
     Some(date.and_time(time))
 }
 
 pub fn extract_time(input: &str) -> Option<NaiveTime> {
-    use regex::Regex;
 
     // Look for time patterns: HH:MM, HH:MMAM, HH:MMPM, H:MM, etc.
     let pattern = r"(\d{1,2}):(\d{2})\s*(am|pm)?";
@@ -280,8 +1326,15 @@ pub fn extract_time(input: &str) -> Option<NaiveTime> {
     None
 }
 
-pub fn extract_date(input: &str) -> NaiveDate {
-    // Try to parse dates like "October 9, 2025" or "2025-10-09"
+pub fn extract_date(input: &str, clock: &dyn Clock) -> NaiveDate {
+    // Default to today if no date found
+    parse_date(input, clock).unwrap_or_else(|| clock.today())
+}
+
+// A date anywhere in the input: "October 9, 2025", "2025-10-09", or one
+// relative to today such as "tomorrow" or "next friday"
+pub fn parse_date(input: &str, clock: &dyn Clock) -> Option<NaiveDate> {
+    let input = &input.to_lowercase();
 
     // Month names mapping
     let months = [
@@ -316,7 +1369,7 @@ pub fn extract_date(input: &str) -> NaiveDate {
 
             if let (Some(d), Some(y)) = (day, year) {
                 if let Some(date) = NaiveDate::from_ymd_opt(y, *month_num, d) {
-                    return date;
+                    return Some(date);
                 }
             }
         }
@@ -324,11 +1377,74 @@ pub fn extract_date(input: &str) -> NaiveDate {
 
     // Try YYYY-MM-DD format
     if let Ok(date) = NaiveDate::parse_from_str(input.split_whitespace().next().unwrap_or(""), "%Y-%m-%d") {
-        return date;
+        return Some(date);
     }
 
-    // Default to today if no date found
-    Local::now().date_naive()
+    relative_date(input, clock)
+}
+
+// "today", "tomorrow", "yesterday", "in 2 weeks", "3 days ago", "end of
+// month", or a weekday: "friday" is the next one (today included), "last
+// friday" the most recent one before today, "this" and "next friday" the one
+// in this and next week, weeks starting on the configured week_start
+fn relative_date(input: &str, clock: &dyn Clock) -> Option<NaiveDate> {
+    let today = clock.today();
+    let find = |pattern: &str| Regex::new(pattern).expect("valid regex").captures(input);
+    let days_since = |a: Weekday, b: Weekday| i64::from((7 + a.num_days_from_monday() - b.num_days_from_monday()) % 7);
+    let week_start = config::active().week_start;
+    let start_of_week = today - Duration::days(days_since(today.weekday(), week_start));
+
+    if find(r"\btoday\b").is_some() {
+        return Some(today);
+    }
+    if find(r"\btomorrow\b").is_some() {
+        return today.succ_opt();
+    }
+    if find(r"\byesterday\b").is_some() {
+        return today.pred_opt();
+    }
+    if let Some(caps) = find(r"\bin (\d+) (day|week|month|year)s?\b") {
+        return shift_date(today, caps[1].parse().ok()?, &caps[2]);
+    }
+    if let Some(caps) = find(r"\b(\d+) (day|week|month|year)s? ago\b") {
+        return shift_date(today, -caps[1].parse::<i64>().ok()?, &caps[2]);
+    }
+    if let Some(caps) = find(r"\bend of (?:the )?(week|month|year)\b") {
+        return match &caps[1] {
+            "week" => Some(start_of_week + Duration::days(6)),
+            "month" => shift_date(today.with_day(1)?, 1, "month")?.pred_opt(),
+            _ => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        };
+    }
+
+    let caps = find(
+        r"\b(?:(this|next|last) )?(monday|mon|tuesday|tues|tue|wednesday|wed|thursday|thurs|thu|friday|fri|saturday|sat|sunday|sun)\b",
+    )?;
+    let weekday: Weekday = caps[2][..3].parse().ok()?;
+    let this_week = start_of_week + Duration::days(days_since(weekday, week_start));
+    match caps.get(1).map(|word| word.as_str()) {
+        Some("this") => Some(this_week),
+        Some("next") => Some(this_week + Duration::weeks(1)),
+        // The most recent one before today, which may be in this week
+        Some("last") => Some(today - Duration::days((days_since(today.weekday(), weekday) + 6) % 7 + 1)),
+        _ => Some(today + Duration::days(days_since(weekday, today.weekday()))),
+    }
+}
+
+// `date` moved by `amount` days, weeks, months or years; a month end that
+// does not exist in the target month is clamped, Jan 31 + 1 month = Feb 28
+fn shift_date(date: NaiveDate, amount: i64, unit: &str) -> Option<NaiveDate> {
+    let months = match unit {
+        "day" => return date.checked_add_signed(Duration::try_days(amount)?),
+        "week" => return date.checked_add_signed(Duration::try_weeks(amount)?),
+        "month" => amount,
+        _ => amount.checked_mul(12)?,
+    };
+    let shift = chrono::Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    match months < 0 {
+        true => date.checked_sub_months(shift),
+        false => date.checked_add_months(shift),
+    }
 }
 
 pub fn get_last_day_of_month(year: i32, month: u32) -> NaiveDate {
@@ -340,33 +1456,3 @@ pub fn get_last_day_of_month(year: i32, month: u32) -> NaiveDate {
         .pred_opt()
         .unwrap()
 }
-
-pub fn print_help() {
-    println!("\nTIME CALCULATOR CLI");
-    println!("=========================================================");
-    println!("\nFUTURE/PAST DATES:");
-    println!("  timecalc future 69 days    - Calculate date 69 days from now");
-    println!("  timecalc future 69d        - Short form");
-    println!("  timecalc past 30 days      - Calculate date 30 days ago");
-
-    println!("\nTIMEZONE CONVERSION:");
-    println!("  timecalc convert 4:00 UTC+7 to WIB");
-    println!("  timecalc convert 10:00 PST to WIB");
-    println!("  timecalc tz 14:30 WIB to UTC");
-
-    println!("\nREMAINING DAYS:");
-    println!("  timecalc remaining month   - Days left in current month");
-    println!("  timecalc remaining year    - Days left in current year");
-    println!("  timecalc left month        - Same as above");
-
-    println!("\nDAY OF WEEK:");
-    println!("  timecalc day 2025-12-25    - What day is this date?");
-
-    println!("\nSUPPORTED TIMEZONES:");
-    println!("  Indonesia: WIB (UTC+7), WITA (UTC+8), WIT (UTC+9)");
-    println!("  Southeast Asia: SGT (Singapore), MYT (Malaysia)");
-    println!("  Common: UTC, PST (UTC-8), EST (UTC-5), JST (Japan)");
-    println!("  Or use: UTC+7, UTC+8, UTC+9, UTC-7, UTC-8, etc.");
-
-    println!("\n=========================================================\n");
-}
\ No newline at end of file
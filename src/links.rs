@@ -0,0 +1,270 @@
+// Backward-compatible IANA names ("links") and the zone each one stands
+// for, as listed in the tzdata bundled with chrono-tz. chrono-tz keeps links
+// as zones of their own, so "US/Pacific" would otherwise be shown as is.
+
+use chrono_tz::Tz;
+
+const LINKS: &[(Tz, Tz)] = &[
+    (Tz::Africa__Accra, Tz::Africa__Abidjan),
+    (Tz::Africa__Addis_Ababa, Tz::Africa__Nairobi),
+    (Tz::Africa__Asmara, Tz::Africa__Nairobi),
+    (Tz::Africa__Asmera, Tz::Africa__Nairobi),
+    (Tz::Africa__Bamako, Tz::Africa__Abidjan),
+    (Tz::Africa__Bangui, Tz::Africa__Lagos),
+    (Tz::Africa__Banjul, Tz::Africa__Abidjan),
+    (Tz::Africa__Blantyre, Tz::Africa__Maputo),
+    (Tz::Africa__Brazzaville, Tz::Africa__Lagos),
+    (Tz::Africa__Bujumbura, Tz::Africa__Maputo),
+    (Tz::Africa__Conakry, Tz::Africa__Abidjan),
+    (Tz::Africa__Dakar, Tz::Africa__Abidjan),
+    (Tz::Africa__Dar_es_Salaam, Tz::Africa__Nairobi),
+    (Tz::Africa__Djibouti, Tz::Africa__Nairobi),
+    (Tz::Africa__Douala, Tz::Africa__Lagos),
+    (Tz::Africa__Freetown, Tz::Africa__Abidjan),
+    (Tz::Africa__Gaborone, Tz::Africa__Maputo),
+    (Tz::Africa__Harare, Tz::Africa__Maputo),
+    (Tz::Africa__Kampala, Tz::Africa__Nairobi),
+    (Tz::Africa__Kigali, Tz::Africa__Maputo),
+    (Tz::Africa__Kinshasa, Tz::Africa__Lagos),
+    (Tz::Africa__Libreville, Tz::Africa__Lagos),
+    (Tz::Africa__Lome, Tz::Africa__Abidjan),
+    (Tz::Africa__Luanda, Tz::Africa__Lagos),
+    (Tz::Africa__Lubumbashi, Tz::Africa__Maputo),
+    (Tz::Africa__Lusaka, Tz::Africa__Maputo),
+    (Tz::Africa__Malabo, Tz::Africa__Lagos),
+    (Tz::Africa__Maseru, Tz::Africa__Johannesburg),
+    (Tz::Africa__Mbabane, Tz::Africa__Johannesburg),
+    (Tz::Africa__Mogadishu, Tz::Africa__Nairobi),
+    (Tz::Africa__Niamey, Tz::Africa__Lagos),
+    (Tz::Africa__Nouakchott, Tz::Africa__Abidjan),
+    (Tz::Africa__Ouagadougou, Tz::Africa__Abidjan),
+    (Tz::Africa__PortoNovo, Tz::Africa__Lagos),
+    (Tz::Africa__Timbuktu, Tz::Africa__Abidjan),
+    (Tz::America__Anguilla, Tz::America__Puerto_Rico),
+    (Tz::America__Antigua, Tz::America__Puerto_Rico),
+    (Tz::America__Argentina__ComodRivadavia, Tz::America__Argentina__Catamarca),
+    (Tz::America__Aruba, Tz::America__Puerto_Rico),
+    (Tz::America__Atikokan, Tz::America__Panama),
+    (Tz::America__Atka, Tz::America__Adak),
+    (Tz::America__BlancSablon, Tz::America__Puerto_Rico),
+    (Tz::America__Buenos_Aires, Tz::America__Argentina__Buenos_Aires),
+    (Tz::America__Catamarca, Tz::America__Argentina__Catamarca),
+    (Tz::America__Cayman, Tz::America__Panama),
+    (Tz::America__Coral_Harbour, Tz::America__Panama),
+    (Tz::America__Cordoba, Tz::America__Argentina__Cordoba),
+    (Tz::America__Creston, Tz::America__Phoenix),
+    (Tz::America__Curacao, Tz::America__Puerto_Rico),
+    (Tz::America__Dominica, Tz::America__Puerto_Rico),
+    (Tz::America__Ensenada, Tz::America__Tijuana),
+    (Tz::America__Fort_Wayne, Tz::America__Indiana__Indianapolis),
+    (Tz::America__Godthab, Tz::America__Nuuk),
+    (Tz::America__Grenada, Tz::America__Puerto_Rico),
+    (Tz::America__Guadeloupe, Tz::America__Puerto_Rico),
+    (Tz::America__Indianapolis, Tz::America__Indiana__Indianapolis),
+    (Tz::America__Jujuy, Tz::America__Argentina__Jujuy),
+    (Tz::America__Knox_IN, Tz::America__Indiana__Knox),
+    (Tz::America__Kralendijk, Tz::America__Puerto_Rico),
+    (Tz::America__Louisville, Tz::America__Kentucky__Louisville),
+    (Tz::America__Lower_Princes, Tz::America__Puerto_Rico),
+    (Tz::America__Marigot, Tz::America__Puerto_Rico),
+    (Tz::America__Mendoza, Tz::America__Argentina__Mendoza),
+    (Tz::America__Montreal, Tz::America__Toronto),
+    (Tz::America__Montserrat, Tz::America__Puerto_Rico),
+    (Tz::America__Nassau, Tz::America__Toronto),
+    (Tz::America__Nipigon, Tz::America__Toronto),
+    (Tz::America__Pangnirtung, Tz::America__Iqaluit),
+    (Tz::America__Port_of_Spain, Tz::America__Puerto_Rico),
+    (Tz::America__Porto_Acre, Tz::America__Rio_Branco),
+    (Tz::America__Rainy_River, Tz::America__Winnipeg),
+    (Tz::America__Rosario, Tz::America__Argentina__Cordoba),
+    (Tz::America__Santa_Isabel, Tz::America__Tijuana),
+    (Tz::America__Shiprock, Tz::America__Denver),
+    (Tz::America__St_Barthelemy, Tz::America__Puerto_Rico),
+    (Tz::America__St_Kitts, Tz::America__Puerto_Rico),
+    (Tz::America__St_Lucia, Tz::America__Puerto_Rico),
+    (Tz::America__St_Thomas, Tz::America__Puerto_Rico),
+    (Tz::America__St_Vincent, Tz::America__Puerto_Rico),
+    (Tz::America__Thunder_Bay, Tz::America__Toronto),
+    (Tz::America__Tortola, Tz::America__Puerto_Rico),
+    (Tz::America__Virgin, Tz::America__Puerto_Rico),
+    (Tz::America__Yellowknife, Tz::America__Edmonton),
+    (Tz::Antarctica__DumontDUrville, Tz::Pacific__Port_Moresby),
+    (Tz::Antarctica__McMurdo, Tz::Pacific__Auckland),
+    (Tz::Antarctica__South_Pole, Tz::Pacific__Auckland),
+    (Tz::Antarctica__Syowa, Tz::Asia__Riyadh),
+    (Tz::Arctic__Longyearbyen, Tz::Europe__Berlin),
+    (Tz::Asia__Aden, Tz::Asia__Riyadh),
+    (Tz::Asia__Ashkhabad, Tz::Asia__Ashgabat),
+    (Tz::Asia__Bahrain, Tz::Asia__Qatar),
+    (Tz::Asia__Brunei, Tz::Asia__Kuching),
+    (Tz::Asia__Calcutta, Tz::Asia__Kolkata),
+    (Tz::Asia__Choibalsan, Tz::Asia__Ulaanbaatar),
+    (Tz::Asia__Chongqing, Tz::Asia__Shanghai),
+    (Tz::Asia__Chungking, Tz::Asia__Shanghai),
+    (Tz::Asia__Dacca, Tz::Asia__Dhaka),
+    (Tz::Asia__Harbin, Tz::Asia__Shanghai),
+    (Tz::Asia__Istanbul, Tz::Europe__Istanbul),
+    (Tz::Asia__Kashgar, Tz::Asia__Urumqi),
+    (Tz::Asia__Katmandu, Tz::Asia__Kathmandu),
+    (Tz::Asia__Kuala_Lumpur, Tz::Asia__Singapore),
+    (Tz::Asia__Kuwait, Tz::Asia__Riyadh),
+    (Tz::Asia__Macao, Tz::Asia__Macau),
+    (Tz::Asia__Muscat, Tz::Asia__Dubai),
+    (Tz::Asia__Phnom_Penh, Tz::Asia__Bangkok),
+    (Tz::Asia__Rangoon, Tz::Asia__Yangon),
+    (Tz::Asia__Saigon, Tz::Asia__Ho_Chi_Minh),
+    (Tz::Asia__Tel_Aviv, Tz::Asia__Jerusalem),
+    (Tz::Asia__Thimbu, Tz::Asia__Thimphu),
+    (Tz::Asia__Ujung_Pandang, Tz::Asia__Makassar),
+    (Tz::Asia__Ulan_Bator, Tz::Asia__Ulaanbaatar),
+    (Tz::Asia__Vientiane, Tz::Asia__Bangkok),
+    (Tz::Atlantic__Faeroe, Tz::Atlantic__Faroe),
+    (Tz::Atlantic__Jan_Mayen, Tz::Europe__Berlin),
+    (Tz::Atlantic__Reykjavik, Tz::Africa__Abidjan),
+    (Tz::Atlantic__St_Helena, Tz::Africa__Abidjan),
+    (Tz::Australia__ACT, Tz::Australia__Sydney),
+    (Tz::Australia__Canberra, Tz::Australia__Sydney),
+    (Tz::Australia__Currie, Tz::Australia__Hobart),
+    (Tz::Australia__LHI, Tz::Australia__Lord_Howe),
+    (Tz::Australia__NSW, Tz::Australia__Sydney),
+    (Tz::Australia__North, Tz::Australia__Darwin),
+    (Tz::Australia__Queensland, Tz::Australia__Brisbane),
+    (Tz::Australia__South, Tz::Australia__Adelaide),
+    (Tz::Australia__Tasmania, Tz::Australia__Hobart),
+    (Tz::Australia__Victoria, Tz::Australia__Melbourne),
+    (Tz::Australia__West, Tz::Australia__Perth),
+    (Tz::Australia__Yancowinna, Tz::Australia__Broken_Hill),
+    (Tz::Brazil__Acre, Tz::America__Rio_Branco),
+    (Tz::Brazil__DeNoronha, Tz::America__Noronha),
+    (Tz::Brazil__East, Tz::America__Sao_Paulo),
+    (Tz::Brazil__West, Tz::America__Manaus),
+    (Tz::CET, Tz::Europe__Brussels),
+    (Tz::CST6CDT, Tz::America__Chicago),
+    (Tz::Canada__Atlantic, Tz::America__Halifax),
+    (Tz::Canada__Central, Tz::America__Winnipeg),
+    (Tz::Canada__Eastern, Tz::America__Toronto),
+    (Tz::Canada__Mountain, Tz::America__Edmonton),
+    (Tz::Canada__Newfoundland, Tz::America__St_Johns),
+    (Tz::Canada__Pacific, Tz::America__Vancouver),
+    (Tz::Canada__Saskatchewan, Tz::America__Regina),
+    (Tz::Canada__Yukon, Tz::America__Whitehorse),
+    (Tz::Chile__Continental, Tz::America__Santiago),
+    (Tz::Chile__EasterIsland, Tz::Pacific__Easter),
+    (Tz::Cuba, Tz::America__Havana),
+    (Tz::EET, Tz::Europe__Athens),
+    (Tz::EST, Tz::America__Panama),
+    (Tz::EST5EDT, Tz::America__New_York),
+    (Tz::Egypt, Tz::Africa__Cairo),
+    (Tz::Eire, Tz::Europe__Dublin),
+    (Tz::Etc__GMTPlus0, Tz::Etc__GMT),
+    (Tz::Etc__GMTMinus0, Tz::Etc__GMT),
+    (Tz::Etc__GMT0, Tz::Etc__GMT),
+    (Tz::Etc__Greenwich, Tz::Etc__GMT),
+    (Tz::Etc__UCT, Tz::Etc__UTC),
+    (Tz::Etc__Universal, Tz::Etc__UTC),
+    (Tz::Etc__Zulu, Tz::Etc__UTC),
+    (Tz::Europe__Amsterdam, Tz::Europe__Brussels),
+    (Tz::Europe__Belfast, Tz::Europe__London),
+    (Tz::Europe__Bratislava, Tz::Europe__Prague),
+    (Tz::Europe__Busingen, Tz::Europe__Zurich),
+    (Tz::Europe__Copenhagen, Tz::Europe__Berlin),
+    (Tz::Europe__Guernsey, Tz::Europe__London),
+    (Tz::Europe__Isle_of_Man, Tz::Europe__London),
+    (Tz::Europe__Jersey, Tz::Europe__London),
+    (Tz::Europe__Kiev, Tz::Europe__Kyiv),
+    (Tz::Europe__Ljubljana, Tz::Europe__Belgrade),
+    (Tz::Europe__Luxembourg, Tz::Europe__Brussels),
+    (Tz::Europe__Mariehamn, Tz::Europe__Helsinki),
+    (Tz::Europe__Monaco, Tz::Europe__Paris),
+    (Tz::Europe__Nicosia, Tz::Asia__Nicosia),
+    (Tz::Europe__Oslo, Tz::Europe__Berlin),
+    (Tz::Europe__Podgorica, Tz::Europe__Belgrade),
+    (Tz::Europe__San_Marino, Tz::Europe__Rome),
+    (Tz::Europe__Sarajevo, Tz::Europe__Belgrade),
+    (Tz::Europe__Skopje, Tz::Europe__Belgrade),
+    (Tz::Europe__Stockholm, Tz::Europe__Berlin),
+    (Tz::Europe__Tiraspol, Tz::Europe__Chisinau),
+    (Tz::Europe__Uzhgorod, Tz::Europe__Kyiv),
+    (Tz::Europe__Vaduz, Tz::Europe__Zurich),
+    (Tz::Europe__Vatican, Tz::Europe__Rome),
+    (Tz::Europe__Zagreb, Tz::Europe__Belgrade),
+    (Tz::Europe__Zaporozhye, Tz::Europe__Kyiv),
+    (Tz::GB, Tz::Europe__London),
+    (Tz::GBEire, Tz::Europe__London),
+    (Tz::GMT, Tz::Etc__GMT),
+    (Tz::GMTPlus0, Tz::Etc__GMT),
+    (Tz::GMTMinus0, Tz::Etc__GMT),
+    (Tz::GMT0, Tz::Etc__GMT),
+    (Tz::Greenwich, Tz::Etc__GMT),
+    (Tz::HST, Tz::Pacific__Honolulu),
+    (Tz::Hongkong, Tz::Asia__Hong_Kong),
+    (Tz::Iceland, Tz::Africa__Abidjan),
+    (Tz::Indian__Antananarivo, Tz::Africa__Nairobi),
+    (Tz::Indian__Christmas, Tz::Asia__Bangkok),
+    (Tz::Indian__Cocos, Tz::Asia__Yangon),
+    (Tz::Indian__Comoro, Tz::Africa__Nairobi),
+    (Tz::Indian__Kerguelen, Tz::Indian__Maldives),
+    (Tz::Indian__Mahe, Tz::Asia__Dubai),
+    (Tz::Indian__Mayotte, Tz::Africa__Nairobi),
+    (Tz::Indian__Reunion, Tz::Asia__Dubai),
+    (Tz::Iran, Tz::Asia__Tehran),
+    (Tz::Israel, Tz::Asia__Jerusalem),
+    (Tz::Jamaica, Tz::America__Jamaica),
+    (Tz::Japan, Tz::Asia__Tokyo),
+    (Tz::Kwajalein, Tz::Pacific__Kwajalein),
+    (Tz::Libya, Tz::Africa__Tripoli),
+    (Tz::MET, Tz::Europe__Brussels),
+    (Tz::MST, Tz::America__Phoenix),
+    (Tz::MST7MDT, Tz::America__Denver),
+    (Tz::Mexico__BajaNorte, Tz::America__Tijuana),
+    (Tz::Mexico__BajaSur, Tz::America__Mazatlan),
+    (Tz::Mexico__General, Tz::America__Mexico_City),
+    (Tz::NZ, Tz::Pacific__Auckland),
+    (Tz::NZCHAT, Tz::Pacific__Chatham),
+    (Tz::Navajo, Tz::America__Denver),
+    (Tz::PRC, Tz::Asia__Shanghai),
+    (Tz::PST8PDT, Tz::America__Los_Angeles),
+    (Tz::Pacific__Chuuk, Tz::Pacific__Port_Moresby),
+    (Tz::Pacific__Enderbury, Tz::Pacific__Kanton),
+    (Tz::Pacific__Funafuti, Tz::Pacific__Tarawa),
+    (Tz::Pacific__Johnston, Tz::Pacific__Honolulu),
+    (Tz::Pacific__Majuro, Tz::Pacific__Tarawa),
+    (Tz::Pacific__Midway, Tz::Pacific__Pago_Pago),
+    (Tz::Pacific__Pohnpei, Tz::Pacific__Guadalcanal),
+    (Tz::Pacific__Ponape, Tz::Pacific__Guadalcanal),
+    (Tz::Pacific__Saipan, Tz::Pacific__Guam),
+    (Tz::Pacific__Samoa, Tz::Pacific__Pago_Pago),
+    (Tz::Pacific__Truk, Tz::Pacific__Port_Moresby),
+    (Tz::Pacific__Wake, Tz::Pacific__Tarawa),
+    (Tz::Pacific__Wallis, Tz::Pacific__Tarawa),
+    (Tz::Pacific__Yap, Tz::Pacific__Port_Moresby),
+    (Tz::Poland, Tz::Europe__Warsaw),
+    (Tz::Portugal, Tz::Europe__Lisbon),
+    (Tz::ROC, Tz::Asia__Taipei),
+    (Tz::ROK, Tz::Asia__Seoul),
+    (Tz::Singapore, Tz::Asia__Singapore),
+    (Tz::Turkey, Tz::Europe__Istanbul),
+    (Tz::UCT, Tz::Etc__UTC),
+    (Tz::US__Alaska, Tz::America__Anchorage),
+    (Tz::US__Aleutian, Tz::America__Adak),
+    (Tz::US__Arizona, Tz::America__Phoenix),
+    (Tz::US__Central, Tz::America__Chicago),
+    (Tz::US__EastIndiana, Tz::America__Indiana__Indianapolis),
+    (Tz::US__Eastern, Tz::America__New_York),
+    (Tz::US__Hawaii, Tz::Pacific__Honolulu),
+    (Tz::US__IndianaStarke, Tz::America__Indiana__Knox),
+    (Tz::US__Michigan, Tz::America__Detroit),
+    (Tz::US__Mountain, Tz::America__Denver),
+    (Tz::US__Pacific, Tz::America__Los_Angeles),
+    (Tz::US__Samoa, Tz::Pacific__Pago_Pago),
+    (Tz::UTC, Tz::Etc__UTC),
+    (Tz::Universal, Tz::Etc__UTC),
+    (Tz::WSU, Tz::Europe__Moscow),
+    (Tz::WET, Tz::Europe__Lisbon),
+    (Tz::Zulu, Tz::Etc__UTC),
+];
+
+// The canonical zone for a link ("Japan" -> "Asia/Tokyo"), any other zone itself
+pub fn canonical(tz: Tz) -> Tz {
+    LINKS.iter().find(|(link, _)| *link == tz).map_or(tz, |(_, target)| *target)
+}
@@ -1,26 +1,90 @@
 use std::env;
-use timecalc::*;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::process;
+
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use timecalc::cli::{self, Cli, Command};
+use timecalc::{batch, config, repl, Clock, Config, TimecalcError};
+use timecalc::completions::COMPLETE_VAR;
+use timecalc::render::{self, OutputFormat};
+
+fn run_batch(file: Option<&Path>, clock: &dyn Clock, format: OutputFormat) -> Result<(), TimecalcError> {
+    let read_failed = |path: &Path, err: io::Error| TimecalcError::ReadFailed {
+        path: path.display().to_string(),
+        reason: err.to_string(),
+    };
+
+    let input: Box<dyn BufRead> = match file {
+        Some(path) if path != Path::new("-") => {
+            Box::new(BufReader::new(File::open(path).map_err(|e| read_failed(path, e))?))
+        }
+        _ => Box::new(io::stdin().lock()),
+    };
+
+    let summary = batch::run(input, clock, format, &mut io::stdout(), &mut io::stderr())
+        .map_err(|e| read_failed(file.unwrap_or(Path::new("-")), e))?;
+
+    if summary.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(TimecalcError::BatchFailed { failed: summary.failed.len(), total: summary.total })
+    }
+}
+
+fn fail(err: TimecalcError, format: OutputFormat) -> ! {
+    if format == OutputFormat::Json {
+        eprintln!("{}", render::error_json(&err));
+    } else {
+        eprint!("{}", render::error_text(&err));
+    }
+    process::exit(err.exit_code());
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    // Loaded first so completion also offers the user's own aliases
+    let loaded = Config::load();
+    if let Ok(config) = &loaded {
+        config::install(config.clone());
+    }
+
+    // Answers the callbacks made by the `timecalc completions` scripts
+    CompleteEnv::with_factory(Cli::command).var(COMPLETE_VAR).complete();
 
-    if args.len() < 2 {
-        print_help();
-        return;
+    let cli = Cli::parse_from(cli::normalize_args(env::args().collect()));
+    if let Err(err) = loaded {
+        fail(err, cli.output.unwrap_or_default());
     }
+    config::install(cli.configured());
+    let format = cli.format();
+
+    let command = match &cli.command {
+        Some(command) => command,
+        None => {
+            cli::print_help();
+            return;
+        }
+    };
+
+    let result = cli.clock().and_then(|clock| match command {
+        Command::Repl => repl::run(clock.as_ref(), format).map(|_| None),
+        Command::Batch { file } => run_batch(file.as_deref(), clock.as_ref(), format).map(|_| None),
+        _ => cli::execute(command, clock.as_ref()).map(Some),
+    });
 
-    let command = args[1].to_lowercase();
-
-    match command.as_str() {
-        "future" | "date" => handle_future_date(&args[2..]),
-        "past" => handle_past_date(&args[2..]),
-        "convert" | "tz" => handle_timezone_convert(&args[2..]),
-        "remaining" | "left" => handle_remaining(&args[2..]),
-        "day" => handle_day_of_week(&args[2..]),
-        "help" | "--help" | "-h" => print_help(),
-        _ => {
-            println!("ERROR: Unknown command: {}", command);
-            print_help();
+    match result {
+        Ok(Some(report)) => {
+            // JSON output carries them in its "warnings" field
+            if format == OutputFormat::Text {
+                for warning in render::warnings(&report) {
+                    eprintln!("WARNING: {}", warning);
+                }
+            }
+            print!("{}", render::render(&report, format));
         }
+        Ok(None) => {}
+        Err(err) => fail(err, format),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,299 @@
+// Offline city and country lookup, so "Tokyo", "Bandung", "New York" or
+// "JP" work wherever a timezone is expected. Cities come from the table
+// below plus the city part of every IANA name (Europe/Berlin -> Berlin).
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use chrono_tz::Tz;
+use chrono_tz::TZ_VARIANTS;
+
+// Cities that are not (or not obviously) an IANA name of their own
+const CITIES: &[(&str, Tz)] = &[
+    // Indonesia: WIB
+    ("Jakarta", Tz::Asia__Jakarta),
+    ("Bandung", Tz::Asia__Jakarta),
+    ("Bogor", Tz::Asia__Jakarta),
+    ("Depok", Tz::Asia__Jakarta),
+    ("Bekasi", Tz::Asia__Jakarta),
+    ("Tangerang", Tz::Asia__Jakarta),
+    ("Semarang", Tz::Asia__Jakarta),
+    ("Yogyakarta", Tz::Asia__Jakarta),
+    ("Jogja", Tz::Asia__Jakarta),
+    ("Solo", Tz::Asia__Jakarta),
+    ("Surakarta", Tz::Asia__Jakarta),
+    ("Surabaya", Tz::Asia__Jakarta),
+    ("Malang", Tz::Asia__Jakarta),
+    ("Medan", Tz::Asia__Jakarta),
+    ("Padang", Tz::Asia__Jakarta),
+    ("Pekanbaru", Tz::Asia__Jakarta),
+    ("Palembang", Tz::Asia__Jakarta),
+    ("Batam", Tz::Asia__Jakarta),
+    ("Bandar Lampung", Tz::Asia__Jakarta),
+    ("Banda Aceh", Tz::Asia__Jakarta),
+    ("Jambi", Tz::Asia__Jakarta),
+    ("Pontianak", Tz::Asia__Pontianak),
+    ("Palangkaraya", Tz::Asia__Pontianak),
+    // Indonesia: WITA
+    ("Makassar", Tz::Asia__Makassar),
+    ("Denpasar", Tz::Asia__Makassar),
+    ("Bali", Tz::Asia__Makassar),
+    ("Mataram", Tz::Asia__Makassar),
+    ("Lombok", Tz::Asia__Makassar),
+    ("Kupang", Tz::Asia__Makassar),
+    ("Balikpapan", Tz::Asia__Makassar),
+    ("Samarinda", Tz::Asia__Makassar),
+    ("Banjarmasin", Tz::Asia__Makassar),
+    ("Manado", Tz::Asia__Makassar),
+    ("Palu", Tz::Asia__Makassar),
+    ("Kendari", Tz::Asia__Makassar),
+    ("Gorontalo", Tz::Asia__Makassar),
+    ("Nusantara", Tz::Asia__Makassar),
+    // Indonesia: WIT
+    ("Jayapura", Tz::Asia__Jayapura),
+    ("Ambon", Tz::Asia__Jayapura),
+    ("Ternate", Tz::Asia__Jayapura),
+    ("Sorong", Tz::Asia__Jayapura),
+    ("Manokwari", Tz::Asia__Jayapura),
+    ("Merauke", Tz::Asia__Jayapura),
+    ("Timika", Tz::Asia__Jayapura),
+
+    // Asia and Oceania
+    ("Osaka", Tz::Asia__Tokyo),
+    ("Kyoto", Tz::Asia__Tokyo),
+    ("Yokohama", Tz::Asia__Tokyo),
+    ("Busan", Tz::Asia__Seoul),
+    ("Beijing", Tz::Asia__Shanghai),
+    ("Shenzhen", Tz::Asia__Shanghai),
+    ("Guangzhou", Tz::Asia__Shanghai),
+    ("Hong Kong", Tz::Asia__Hong_Kong),
+    ("Mumbai", Tz::Asia__Kolkata),
+    ("Bombay", Tz::Asia__Kolkata),
+    ("Delhi", Tz::Asia__Kolkata),
+    ("New Delhi", Tz::Asia__Kolkata),
+    ("Bangalore", Tz::Asia__Kolkata),
+    ("Bengaluru", Tz::Asia__Kolkata),
+    ("Chennai", Tz::Asia__Kolkata),
+    ("Hyderabad", Tz::Asia__Kolkata),
+    ("Pune", Tz::Asia__Kolkata),
+    ("Lahore", Tz::Asia__Karachi),
+    ("Islamabad", Tz::Asia__Karachi),
+    ("Hanoi", Tz::Asia__Bangkok),
+    ("Ho Chi Minh", Tz::Asia__Ho_Chi_Minh),
+    ("Saigon", Tz::Asia__Ho_Chi_Minh),
+    ("Phnom Penh", Tz::Asia__Phnom_Penh),
+    ("Cebu", Tz::Asia__Manila),
+    ("Penang", Tz::Asia__Kuala_Lumpur),
+    ("Johor Bahru", Tz::Asia__Kuala_Lumpur),
+    ("Abu Dhabi", Tz::Asia__Dubai),
+    ("Doha", Tz::Asia__Qatar),
+    ("Tel Aviv", Tz::Asia__Jerusalem),
+    ("Canberra", Tz::Australia__Sydney),
+    ("Gold Coast", Tz::Australia__Brisbane),
+
+    // Europe and Africa
+    ("Manchester", Tz::Europe__London),
+    ("Edinburgh", Tz::Europe__London),
+    ("Munich", Tz::Europe__Berlin),
+    ("Frankfurt", Tz::Europe__Berlin),
+    ("Hamburg", Tz::Europe__Berlin),
+    ("Cologne", Tz::Europe__Berlin),
+    ("Barcelona", Tz::Europe__Madrid),
+    ("Milan", Tz::Europe__Rome),
+    ("Geneva", Tz::Europe__Zurich),
+    ("The Hague", Tz::Europe__Amsterdam),
+    ("Rotterdam", Tz::Europe__Amsterdam),
+    ("Saint Petersburg", Tz::Europe__Moscow),
+    ("St Petersburg", Tz::Europe__Moscow),
+    ("Cape Town", Tz::Africa__Johannesburg),
+
+    // Americas
+    ("New York City", Tz::America__New_York),
+    ("NYC", Tz::America__New_York),
+    ("Boston", Tz::America__New_York),
+    ("Washington", Tz::America__New_York),
+    ("Philadelphia", Tz::America__New_York),
+    ("Atlanta", Tz::America__New_York),
+    ("Miami", Tz::America__New_York),
+    ("Montreal", Tz::America__Toronto),
+    ("Ottawa", Tz::America__Toronto),
+    ("Dallas", Tz::America__Chicago),
+    ("Houston", Tz::America__Chicago),
+    ("Austin", Tz::America__Chicago),
+    ("Minneapolis", Tz::America__Chicago),
+    ("Salt Lake City", Tz::America__Denver),
+    ("San Francisco", Tz::America__Los_Angeles),
+    ("San Jose", Tz::America__Los_Angeles),
+    ("Seattle", Tz::America__Los_Angeles),
+    ("Portland", Tz::America__Los_Angeles),
+    ("San Diego", Tz::America__Los_Angeles),
+    ("Las Vegas", Tz::America__Los_Angeles),
+    ("Sao Paulo", Tz::America__Sao_Paulo),
+    ("São Paulo", Tz::America__Sao_Paulo),
+    ("Rio de Janeiro", Tz::America__Sao_Paulo),
+    ("Brasilia", Tz::America__Sao_Paulo),
+    ("Bogotá", Tz::America__Bogota),
+];
+
+// ISO 3166 codes with their zones, the first one being the capital's
+const COUNTRIES: &[(&str, &str, &[Tz])] = &[
+    ("ID", "Indonesia", &[Tz::Asia__Jakarta, Tz::Asia__Makassar, Tz::Asia__Jayapura]),
+    ("JP", "Japan", &[Tz::Asia__Tokyo]),
+    ("SG", "Singapore", &[Tz::Asia__Singapore]),
+    ("MY", "Malaysia", &[Tz::Asia__Kuala_Lumpur]),
+    ("TH", "Thailand", &[Tz::Asia__Bangkok]),
+    ("VN", "Vietnam", &[Tz::Asia__Ho_Chi_Minh]),
+    ("PH", "Philippines", &[Tz::Asia__Manila]),
+    ("KR", "South Korea", &[Tz::Asia__Seoul]),
+    ("CN", "China", &[Tz::Asia__Shanghai]),
+    ("HK", "Hong Kong", &[Tz::Asia__Hong_Kong]),
+    ("TW", "Taiwan", &[Tz::Asia__Taipei]),
+    ("IN", "India", &[Tz::Asia__Kolkata]),
+    ("PK", "Pakistan", &[Tz::Asia__Karachi]),
+    ("BD", "Bangladesh", &[Tz::Asia__Dhaka]),
+    ("NP", "Nepal", &[Tz::Asia__Kathmandu]),
+    ("LK", "Sri Lanka", &[Tz::Asia__Colombo]),
+    ("AE", "United Arab Emirates", &[Tz::Asia__Dubai]),
+    ("SA", "Saudi Arabia", &[Tz::Asia__Riyadh]),
+    ("QA", "Qatar", &[Tz::Asia__Qatar]),
+    ("IL", "Israel", &[Tz::Asia__Jerusalem]),
+    ("TR", "Turkey", &[Tz::Europe__Istanbul]),
+    ("AU", "Australia", &[
+        Tz::Australia__Sydney, Tz::Australia__Brisbane, Tz::Australia__Adelaide,
+        Tz::Australia__Darwin, Tz::Australia__Perth,
+    ]),
+    ("NZ", "New Zealand", &[Tz::Pacific__Auckland]),
+    ("GB", "United Kingdom", &[Tz::Europe__London]),
+    ("IE", "Ireland", &[Tz::Europe__Dublin]),
+    ("FR", "France", &[Tz::Europe__Paris]),
+    ("DE", "Germany", &[Tz::Europe__Berlin]),
+    ("NL", "Netherlands", &[Tz::Europe__Amsterdam]),
+    ("BE", "Belgium", &[Tz::Europe__Brussels]),
+    ("CH", "Switzerland", &[Tz::Europe__Zurich]),
+    ("AT", "Austria", &[Tz::Europe__Vienna]),
+    ("ES", "Spain", &[Tz::Europe__Madrid, Tz::Atlantic__Canary]),
+    ("PT", "Portugal", &[Tz::Europe__Lisbon, Tz::Atlantic__Azores]),
+    ("IT", "Italy", &[Tz::Europe__Rome]),
+    ("SE", "Sweden", &[Tz::Europe__Stockholm]),
+    ("NO", "Norway", &[Tz::Europe__Oslo]),
+    ("DK", "Denmark", &[Tz::Europe__Copenhagen]),
+    ("FI", "Finland", &[Tz::Europe__Helsinki]),
+    ("PL", "Poland", &[Tz::Europe__Warsaw]),
+    ("UA", "Ukraine", &[Tz::Europe__Kyiv]),
+    ("GR", "Greece", &[Tz::Europe__Athens]),
+    ("RU", "Russia", &[
+        Tz::Europe__Moscow, Tz::Asia__Yekaterinburg, Tz::Asia__Novosibirsk,
+        Tz::Asia__Vladivostok,
+    ]),
+    ("EG", "Egypt", &[Tz::Africa__Cairo]),
+    ("NG", "Nigeria", &[Tz::Africa__Lagos]),
+    ("KE", "Kenya", &[Tz::Africa__Nairobi]),
+    ("ZA", "South Africa", &[Tz::Africa__Johannesburg]),
+    ("US", "United States", &[
+        Tz::America__New_York, Tz::America__Chicago, Tz::America__Denver,
+        Tz::America__Phoenix, Tz::America__Los_Angeles, Tz::America__Anchorage,
+        Tz::Pacific__Honolulu,
+    ]),
+    ("CA", "Canada", &[
+        Tz::America__Toronto, Tz::America__Halifax, Tz::America__Winnipeg,
+        Tz::America__Edmonton, Tz::America__Vancouver,
+    ]),
+    ("MX", "Mexico", &[Tz::America__Mexico_City, Tz::America__Tijuana]),
+    ("BR", "Brazil", &[Tz::America__Sao_Paulo, Tz::America__Manaus]),
+    ("AR", "Argentina", &[Tz::America__Argentina__Buenos_Aires]),
+    ("CL", "Chile", &[Tz::America__Santiago]),
+    ("CO", "Colombia", &[Tz::America__Bogota]),
+    ("PE", "Peru", &[Tz::America__Lima]),
+];
+
+// Other names people use for a country, by code
+const COUNTRY_NICKNAMES: &[(&str, &str)] = &[("USA", "US"), ("UK", "GB"), ("Holland", "NL")];
+
+// Codes that read like words in an expression ("10:00 AM", "in 2 days"),
+// only the country name works for those
+const WORD_CODES: &[&str] = &["AM", "AT", "IN", "IS", "PM", "TO"];
+
+// What a city or country name resolved to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    pub name: String,
+    // More than one zone means the name is a country spanning several
+    pub zones: Vec<Tz>,
+}
+
+// "new_york", "New York" and "new-york" are the same place
+fn key(name: &str) -> String {
+    name.to_lowercase()
+        .replace(['_', '-'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The city part of region-prefixed IANA names, "America/Argentina/Buenos_Aires" -> "Buenos Aires"
+fn iana_cities() -> impl Iterator<Item = (String, Tz)> {
+    const REGIONS: &[&str] = &[
+        "Africa/", "America/", "Antarctica/", "Asia/", "Atlantic/", "Australia/", "Europe/",
+        "Indian/", "Pacific/",
+    ];
+
+    TZ_VARIANTS
+        .iter()
+        .filter(|tz| REGIONS.iter().any(|region| tz.name().starts_with(region)))
+        .filter_map(|tz| tz.name().rsplit('/').next().map(|city| (city.replace('_', " "), *tz)))
+}
+
+// Cities by key, built once: the table first, then IANA names
+static CITY_INDEX: LazyLock<HashMap<String, (String, Tz)>> = LazyLock::new(|| {
+    let mut index = HashMap::new();
+    for (name, tz) in CITIES.iter().map(|(name, tz)| (name.to_string(), *tz)).chain(iana_cities()) {
+        index.entry(key(&name)).or_insert((name, tz));
+    }
+    index
+});
+
+pub fn lookup(input: &str) -> Option<Place> {
+    let wanted = key(input);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    if let Some((name, tz)) = CITY_INDEX.get(&wanted) {
+        return Some(Place { name: name.clone(), zones: vec![*tz] });
+    }
+
+    // Codes are matched upper-case only, "id" or "no" in a sentence are not countries
+    let code = COUNTRY_NICKNAMES
+        .iter()
+        .find(|(nickname, _)| key(nickname) == wanted)
+        .map_or(input.trim(), |(_, code)| *code);
+
+    COUNTRIES
+        .iter()
+        .find(|(c, name, _)| (*c == code && !WORD_CODES.contains(c)) || key(name) == wanted)
+        .map(|(code, name, zones)| Place {
+            name: format!("{} ({})", name, code),
+            zones: zones.to_vec(),
+        })
+}
+
+// Every name lookup() accepts, for completion and suggestions
+pub fn names() -> Vec<String> {
+    NAMES.clone()
+}
+
+static NAMES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let mut names: Vec<String> = CITIES.iter().map(|(name, _)| name.to_string()).collect();
+    names.extend(iana_cities().map(|(name, _)| name));
+    for (code, name, _) in COUNTRIES {
+        names.push(name.to_string());
+        if !WORD_CODES.contains(code) {
+            names.push(code.to_string());
+        }
+    }
+    names.extend(COUNTRY_NICKNAMES.iter().map(|(nickname, _)| nickname.to_string()));
+
+    let mut seen = HashSet::new();
+    names.retain(|name| seen.insert(key(name)));
+    names
+});
@@ -0,0 +1,940 @@
+// Text and JSON rendering for the results computed by the handle_* functions.
+// Everything here is pure formatting, the CLI decides where it gets printed.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use chrono::{DateTime, Offset, Weekday};
+use serde_json::{json, Value};
+
+use crate::config::{self, Config};
+use crate::zone::format_offset;
+use crate::{
+    Conversion, DateCalculation, DayInfo, DayPeriod, Direction, Location, MeetingPlan, MeetingSlot, Participant, Period, RemainingDays,
+    military_letter, Team, TimecalcError, Transition, TransitionList, WorldClock, Zone, ZoneInfo, ZoneList, ZoneOffset,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = TimecalcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(TimecalcError::InvalidOutputFormat(s.to_string())),
+        }
+    }
+}
+
+// Any result a command can produce, so callers can render without caring which
+#[derive(Debug, Clone, PartialEq)]
+pub enum Report {
+    Date(DateCalculation),
+    Conversion(Conversion),
+    Remaining(RemainingDays),
+    Day(DayInfo),
+    Where(Location),
+    Clock(WorldClock),
+    Team(Team),
+    Meeting(MeetingPlan),
+    ZoneInfo(ZoneInfo),
+    Transitions(TransitionList),
+    Zones(ZoneList),
+    Config(Config),
+    // Printed verbatim whatever the output format, e.g. completion scripts
+    Script(String),
+}
+
+pub fn render(report: &Report, format: OutputFormat) -> String {
+    match (report, format) {
+        (Report::Script(script), _) => script.clone(),
+        (_, OutputFormat::Text) => text(report),
+        (_, OutputFormat::Json) => format!("{}\n", json(report)),
+    }
+}
+
+pub fn text(report: &Report) -> String {
+    match report {
+        Report::Date(calc) => date_calculation(calc),
+        Report::Conversion(conv) => conversion(conv),
+        Report::Remaining(rem) => remaining(rem),
+        Report::Day(info) => day_info(info),
+        Report::Where(location) => where_is(location),
+        Report::Clock(world) => world_clock(world),
+        Report::Team(team) => team_status(team),
+        Report::Meeting(plan) => meeting(plan),
+        Report::ZoneInfo(info) => zone_info(info),
+        Report::Transitions(list) => transitions(list),
+        Report::Zones(list) => zones(list),
+        Report::Config(config) => configuration(config),
+        Report::Script(script) => script.clone(),
+    }
+}
+
+// Notes about how the input was read, printed on stderr next to the result
+pub fn warnings(report: &Report) -> &[String] {
+    match report {
+        Report::Conversion(conv) => &conv.warnings,
+        Report::Clock(world) => &world.warnings,
+        Report::Team(team) => &team.warnings,
+        Report::Meeting(plan) => &plan.warnings,
+        Report::ZoneInfo(info) => &info.warnings,
+        Report::Transitions(list) => &list.warnings,
+        _ => &[],
+    }
+}
+
+// Single-line form used by batch mode
+pub fn line(report: &Report) -> String {
+    match report {
+        Report::Date(calc) => format!(
+            "{} {} {} days = {} ({})\n",
+            calc.today.format("%Y-%m-%d"),
+            match calc.direction {
+                Direction::Future => '+',
+                Direction::Past => '-',
+            },
+            calc.days,
+            calc.date.format("%Y-%m-%d"),
+            calc.date.format("%A")
+        ),
+        Report::Conversion(conv) => {
+            let note = conv.dst.as_ref().map_or(String::new(), |note| format!(" ({})", note));
+            let also: String = conv
+                .also
+                .iter()
+                .map(|dest| format!(", {} {}", dated_time(&dest.datetime), dest.label))
+                .collect();
+            format!(
+                "{} {} -> {} {}{}{}\n",
+                dated_time(&conv.from),
+                conv.from_label,
+                dated_time(&conv.to),
+                conv.to_label,
+                also,
+                note
+            )
+        }
+        Report::Remaining(rem) => format!(
+            "{} {}: {} days remaining until {}, {} days passed\n",
+            rem.today,
+            match rem.period {
+                Period::Month => "month",
+                Period::Year => "year",
+            },
+            rem.remaining,
+            rem.end,
+            rem.passed
+        ),
+        Report::Day(info) => format!("{} {}\n", info.date, info.date.format("%A")),
+        Report::Where(location) => location
+            .times
+            .iter()
+            .map(|dt| {
+                format!(
+                    "{} {} {} {}\n",
+                    location.name,
+                    dt.timezone().name(),
+                    dt.format("%Z"),
+                    dt.format(&format!("%Y-%m-%d {}", config::active().time_format.pattern()))
+                )
+            })
+            .collect(),
+        Report::Clock(world) => world
+            .times
+            .iter()
+            .map(|dest| {
+                format!(
+                    "{} {} {} {} {}\n",
+                    dest.label,
+                    dated_time(&dest.datetime),
+                    dest.datetime.format("%Z"),
+                    dest.datetime.format("%:z"),
+                    DayPeriod::of(&dest.datetime).name()
+                )
+            })
+            .collect(),
+        Report::Team(team) => team
+            .members
+            .iter()
+            .map(|member| {
+                format!(
+                    "{} {} {} {} {}\n",
+                    member.name,
+                    dated_time(&member.datetime),
+                    member.label,
+                    member_state(member),
+                    member.change.map_or("never".to_string(), |change| change.to_rfc3339())
+                )
+            })
+            .collect(),
+        Report::Meeting(plan) => match plan.slots.first() {
+            Some(best) => format!(
+                "{} {}\n",
+                plan.date,
+                plan.participants
+                    .iter()
+                    .map(|participant| format!("{} {}", participant.label, slot_span(best, participant)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            None => format!("{} no overlap\n", plan.date),
+        },
+        Report::ZoneInfo(info) => format!(
+            "{} {} {} standard {} dst {} next {}\n",
+            info.now.timezone().name(),
+            info.now.format("%Z"),
+            info.now.format("%:z"),
+            offset_name(&info.standard),
+            info.dst.as_ref().map_or("none".to_string(), offset_name),
+            info.next.as_ref().map_or("none".to_string(), |t| t.at.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        ),
+        Report::Transitions(list) => {
+            let time = config::active().time_format.pattern();
+            list.transitions
+                .iter()
+                .map(|transition| format!("{} {}\n", list.zone.name(), transition_line(transition, time)))
+                .collect()
+        }
+        Report::Zones(list) => list
+            .zones
+            .iter()
+            .map(|dt| format!("{} {} {}\n", dt.timezone().name(), dt.format("%:z"), dt.format("%Z")))
+            .collect(),
+        Report::Config(config) => configuration(config),
+        Report::Script(script) => script.clone(),
+    }
+}
+
+// The JSON schema is documented in the README, keep the two in sync
+pub fn json(report: &Report) -> Value {
+    match report {
+        Report::Date(calc) => json!({
+            "command": match calc.direction {
+                Direction::Future => "future",
+                Direction::Past => "past",
+            },
+            "days": calc.days,
+            "zone": calc.zone.name(),
+            "today": calc.today.to_rfc3339(),
+            "date": calc.date.to_rfc3339(),
+            "weekday": calc.date.format("%A").to_string(),
+        }),
+        Report::Conversion(conv) => json!({
+            "command": "convert",
+            "from": zoned_json(&conv.from, &conv.from_label),
+            "to": zoned_json(&conv.to, &conv.to_label),
+            "difference_minutes": offset_minutes(&conv.from, &conv.to),
+            "day_change": day_change(&conv.from, &conv.to),
+            "destinations": std::iter::once((&conv.to, &conv.to_label))
+                .chain(conv.also.iter().map(|dest| (&dest.datetime, &dest.label)))
+                .map(|(dt, label)| {
+                    let mut value = zoned_json(dt, label);
+                    value["difference_minutes"] = json!(offset_minutes(&conv.from, dt));
+                    value["day_change"] = json!(day_change(&conv.from, dt));
+                    value
+                })
+                .collect::<Vec<_>>(),
+            "warnings": conv.warnings,
+            "dst": conv.dst,
+        }),
+        Report::Remaining(rem) => json!({
+            "command": "remaining",
+            "period": match rem.period {
+                Period::Month => "month",
+                Period::Year => "year",
+            },
+            "zone": rem.zone.name(),
+            "today": rem.today.to_string(),
+            "end": rem.end.to_string(),
+            "days_remaining": rem.remaining,
+            "days_passed": rem.passed,
+        }),
+        Report::Day(info) => json!({
+            "command": "day",
+            "date": info.date.to_string(),
+            "weekday": info.date.format("%A").to_string(),
+        }),
+        Report::Where(location) => json!({
+            "command": "where",
+            "place": location.name,
+            "zones": location
+                .times
+                .iter()
+                .map(|dt| json!({
+                    "zone": dt.timezone().name(),
+                    "abbreviation": dt.format("%Z").to_string(),
+                    "offset": dt.format("%:z").to_string(),
+                    "datetime": dt.to_rfc3339(),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        Report::Clock(world) => json!({
+            "command": "now",
+            "zones": world
+                .times
+                .iter()
+                .map(|dest| {
+                    let mut value = zoned_json(&dest.datetime, &dest.label);
+                    value["period"] = json!(match DayPeriod::of(&dest.datetime) {
+                        DayPeriod::Working => "working",
+                        DayPeriod::Daytime => "daytime",
+                        DayPeriod::Night => "night",
+                    });
+                    value
+                })
+                .collect::<Vec<_>>(),
+            "warnings": world.warnings,
+        }),
+        Report::Team(team) => json!({
+            "command": "team",
+            "working": team.members.iter().filter(|member| member.working).count(),
+            "members": team
+                .members
+                .iter()
+                .map(|member| {
+                    let mut value = zoned_json(&member.datetime, &member.label);
+                    value["name"] = json!(member.name);
+                    value["hours"] = json!(member.hours.to_string());
+                    value["state"] = json!(member_state(member));
+                    value["working"] = json!(member.working);
+                    value["day_off"] = json!(member.day_off);
+                    value["next_change"] = json!(member.change.map(|change| change.to_rfc3339()));
+                    value["minutes_until_change"] =
+                        json!(member.change.map(|change| (change - member.datetime).num_minutes()));
+                    value
+                })
+                .collect::<Vec<_>>(),
+            "warnings": team.warnings,
+        }),
+        Report::Meeting(plan) => json!({
+            "command": "meet",
+            "date": plan.date.to_string(),
+            "hours": plan.hours.to_string(),
+            "duration_minutes": plan.length.num_minutes(),
+            "participants": plan
+                .participants
+                .iter()
+                .map(|participant| json!({ "zone": participant.zone.name(), "label": participant.label }))
+                .collect::<Vec<_>>(),
+            "windows": plan
+                .windows
+                .iter()
+                .map(|(start, end)| json!({
+                    "start": start.to_rfc3339(),
+                    "end": end.to_rfc3339(),
+                    "minutes": (*end - *start).num_minutes(),
+                }))
+                .collect::<Vec<_>>(),
+            "slots": plan
+                .slots
+                .iter()
+                .map(|slot| json!({
+                    "start": slot.start.to_rfc3339(),
+                    "end": slot.end.to_rfc3339(),
+                    "margin_minutes": slot.margin.num_minutes(),
+                    "local": plan
+                        .participants
+                        .iter()
+                        .map(|participant| json!({
+                            "label": participant.label,
+                            "start": slot.start.with_timezone(&participant.zone).to_rfc3339(),
+                            "end": slot.end.with_timezone(&participant.zone).to_rfc3339(),
+                        }))
+                        .collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+            "warnings": plan.warnings,
+        }),
+        Report::ZoneInfo(info) => json!({
+            "command": "zone",
+            "zone": info.now.timezone().name(),
+            "datetime": info.now.to_rfc3339(),
+            "abbreviation": info.now.format("%Z").to_string(),
+            "offset": info.now.format("%:z").to_string(),
+            "standard": offset_json(&info.standard),
+            "dst": info.dst.as_ref().map(offset_json),
+            "dst_active": info.now.offset().is_dst(),
+            "previous": info.previous.as_ref().map(transition_json),
+            "next": info.next.as_ref().map(transition_json),
+            "warnings": info.warnings,
+        }),
+        Report::Transitions(list) => json!({
+            "command": "transitions",
+            "zone": list.zone.name(),
+            "from": list.from.to_string(),
+            "to": list.to.to_string(),
+            "transitions": list.transitions.iter().map(|transition| {
+                let mut value = transition_json(transition);
+                value["local_before"] = json!(transition.local_before().format("%Y-%m-%dT%H:%M:%S").to_string());
+                value["local_after"] = json!(transition.local_after().format("%Y-%m-%dT%H:%M:%S").to_string());
+                value
+            }).collect::<Vec<_>>(),
+            "warnings": list.warnings,
+        }),
+        Report::Zones(list) => json!({
+            "command": "zones",
+            "search": list.search,
+            "zones": list
+                .zones
+                .iter()
+                .map(|dt| json!({
+                    "zone": dt.timezone().name(),
+                    "abbreviation": dt.format("%Z").to_string(),
+                    "offset": dt.format("%:z").to_string(),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        Report::Config(config) => json!({
+            "command": "config",
+            "path": config.path.as_ref().map(|path| path.display().to_string()),
+            "loaded": config.loaded,
+            "default_zone": config.default_zone,
+            "output": match config.output {
+                OutputFormat::Text => "text",
+                OutputFormat::Json => "json",
+            },
+            "time_format": config.time_format.name(),
+            "week_start": weekday_name(config.week_start),
+            "prefer_regions": config.prefer_regions,
+            "strict": config.strict,
+            "dst": config.dst.name(),
+            "military": config.military,
+            "clock_zones": config.clock_zones,
+            "team": config
+                .team
+                .iter()
+                .map(|member| json!({
+                    "name": member.name,
+                    "zone": member.zone,
+                    "hours": member.hours.to_string(),
+                    "weekend": member.weekend.iter().map(|day| weekday_name(*day)).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+            "aliases": config
+                .aliases
+                .iter()
+                .map(|(alias, zone)| (alias.clone(), Value::from(zone.name())))
+                .collect::<serde_json::Map<_, _>>(),
+        }),
+        Report::Script(script) => json!({ "script": script }),
+    }
+}
+
+// "ERROR: ..." followed by the hint lines, if any
+pub fn error_text(err: &TimecalcError) -> String {
+    match err.hint() {
+        Some(hint) => format!("ERROR: {}\n{}\n", err, hint),
+        None => format!("ERROR: {}\n", err),
+    }
+}
+
+pub fn error_json(err: &TimecalcError) -> Value {
+    json!({
+        "error": {
+            "kind": err.kind(),
+            "code": err.exit_code(),
+            "message": err.to_string(),
+        }
+    })
+}
+
+// A time of day in the configured format, or "0400Z" with --military when
+// the offset has a zone letter
+fn clock_time(dt: &DateTime<Zone>) -> String {
+    let config = config::active();
+    match military_letter(dt.offset().fix()) {
+        Some(letter) if config.military => format!("{}{}", dt.format("%H%M"), letter),
+        _ => dt.format(config.time_format.pattern()).to_string(),
+    }
+}
+
+// "2025-10-09 04:00", or "2025-10-09 0400Z"
+fn dated_time(dt: &DateTime<Zone>) -> String {
+    format!("{} {}", dt.format("%Y-%m-%d"), clock_time(dt))
+}
+
+fn offset_json(offset: &ZoneOffset) -> Value {
+    json!({
+        "abbreviation": offset.to_string(),
+        "offset": offset.fix().to_string(),
+    })
+}
+
+fn transition_json(transition: &Transition) -> Value {
+    json!({
+        "at": transition.at.to_rfc3339(),
+        "before": offset_json(&transition.before),
+        "after": offset_json(&transition.after),
+    })
+}
+
+fn zoned_json(dt: &DateTime<Zone>, label: &str) -> Value {
+    json!({
+        "datetime": dt.to_rfc3339(),
+        "zone": dt.timezone().name(),
+        "label": label,
+        "abbreviation": dt.format("%Z").to_string(),
+        "offset": dt.format("%:z").to_string(),
+    })
+}
+
+const RULE: &str = "=====================================";
+
+pub fn date_calculation(calc: &DateCalculation) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "\nDATE CALCULATION").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "TODAY:        {} ({})",
+             calc.today.format("%A, %B %d, %Y"),
+             calc.today.format("%Y-%m-%d")).unwrap();
+    writeln!(out, "ZONE:         {} (local)", calc.zone).unwrap();
+    match calc.direction {
+        Direction::Future => writeln!(out, "AFTER {} DAYS: {} ({})",
+                                      calc.days,
+                                      calc.date.format("%A, %B %d, %Y"),
+                                      calc.date.format("%Y-%m-%d")).unwrap(),
+        Direction::Past => writeln!(out, "{} DAYS AGO:  {} ({})",
+                                    calc.days,
+                                    calc.date.format("%A, %B %d, %Y"),
+                                    calc.date.format("%Y-%m-%d")).unwrap(),
+    }
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+pub fn conversion(conv: &Conversion) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "\nTIMEZONE CONVERSION").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "FROM: {} {} {}",
+             conv.from.format("%A, %B %d, %Y"),
+             clock_time(&conv.from),
+             zone_text(&conv.from, &conv.from_label)).unwrap();
+    writeln!(out, "TO:   {} {} {}  {}",
+             conv.to.format("%A, %B %d, %Y"),
+             clock_time(&conv.to),
+             zone_text(&conv.to, &conv.to_label),
+             difference(&conv.from, &conv.to)).unwrap();
+    for dest in &conv.also {
+        writeln!(out, "      {} {} {}  {}",
+                 dest.datetime.format("%A, %B %d, %Y"),
+                 clock_time(&dest.datetime),
+                 zone_text(&dest.datetime, &dest.label),
+                 difference(&conv.from, &dest.datetime)).unwrap();
+    }
+    if let Some(note) = &conv.dst {
+        writeln!(out, "NOTE: {}", note).unwrap();
+    }
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+// The abbreviation in effect and the offset, "PDT (UTC-07:00)", followed by
+// the zone as it was asked for when that reads differently: "[PST]",
+// "[New York]". Fixed offsets and zones without an abbreviation show the
+// name as asked for instead.
+fn zone_text(dt: &DateTime<Zone>, label: &str) -> String {
+    let offset = format_offset(&dt.offset().fix());
+    match dt.offset().abbreviation() {
+        Some(abbreviation) if abbreviation.eq_ignore_ascii_case(label) => format!("{} ({})", abbreviation, offset),
+        Some(abbreviation) => format!("{} ({}) [{}]", abbreviation, offset, label),
+        None => format!("{} ({})", label, offset),
+    }
+}
+
+// How far `to` is ahead of or behind `from`, and the date change if any:
+// "+7h", "-15h, -1 day", "+5h30m"
+fn difference(from: &DateTime<Zone>, to: &DateTime<Zone>) -> String {
+    let minutes = offset_minutes(from, to);
+    let (hours, rest) = (minutes.abs() / 60, minutes.abs() % 60);
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let mut text = match rest {
+        0 => format!("{}{}h", sign, hours),
+        _ if hours == 0 => format!("{}{}m", sign, rest),
+        _ => format!("{}{}h{}m", sign, hours, rest),
+    };
+    match day_change(from, to) {
+        0 => {}
+        days @ (1 | -1) => write!(text, ", {:+} day", days).unwrap(),
+        days => write!(text, ", {:+} days", days).unwrap(),
+    }
+    text
+}
+
+fn offset_minutes(from: &DateTime<Zone>, to: &DateTime<Zone>) -> i32 {
+    (to.offset().fix().local_minus_utc() - from.offset().fix().local_minus_utc()) / 60
+}
+
+fn day_change(from: &DateTime<Zone>, to: &DateTime<Zone>) -> i64 {
+    to.date_naive().signed_duration_since(from.date_naive()).num_days()
+}
+
+pub fn remaining(rem: &RemainingDays) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "\nDAYS REMAINING").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "TODAY:           {}", rem.today.format("%B %d, %Y")).unwrap();
+    writeln!(out, "ZONE:            {} (local)", rem.zone).unwrap();
+    match rem.period {
+        Period::Month => writeln!(out, "END OF MONTH:    {}", rem.end.format("%B %d, %Y")).unwrap(),
+        Period::Year => writeln!(out, "END OF YEAR:     {}", rem.end.format("%B %d, %Y")).unwrap(),
+    }
+    writeln!(out, "DAYS REMAINING:  {} days", rem.remaining).unwrap();
+    writeln!(out, "DAYS PASSED:     {} days", rem.passed).unwrap();
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+pub fn day_info(info: &DayInfo) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "\nDAY OF WEEK").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "DATE: {}", info.date.format("%B %d, %Y")).unwrap();
+    writeln!(out, "DAY:  {}", info.date.format("%A")).unwrap();
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+pub fn where_is(location: &Location) -> String {
+    let mut out = String::new();
+    let time = config::active().time_format.pattern();
+
+    writeln!(out, "\nWHERE").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "PLACE: {}", location.name).unwrap();
+    for dt in &location.times {
+        writeln!(out, "ZONE:  {} {} ({})", dt.timezone().name(), dt.format("%Z"), format_offset(&dt.offset().fix())).unwrap();
+        writeln!(out, "TIME:  {} {}", dt.format("%A, %B %d, %Y"), dt.format(time)).unwrap();
+    }
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+pub fn world_clock(world: &WorldClock) -> String {
+    let mut out = String::new();
+    let width = world.times.iter().map(|dest| dest.label.len()).max().unwrap_or(0);
+
+    writeln!(out, "\nWORLD CLOCK").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    for dest in &world.times {
+        writeln!(out, "{:<width$}  {} {} {} ({})  {}",
+                 dest.label,
+                 dest.datetime.format("%a, %b %d, %Y"),
+                 clock_time(&dest.datetime),
+                 dest.datetime.format("%Z"),
+                 format_offset(&dest.datetime.offset().fix()),
+                 DayPeriod::of(&dest.datetime).name()).unwrap();
+    }
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+// "working", "off" or "weekend"
+fn member_state(member: &crate::MemberStatus) -> &'static str {
+    match (member.working, member.day_off) {
+        (true, _) => "working",
+        (false, true) => "weekend",
+        (false, false) => "off",
+    }
+}
+
+// "45m", "7h30m", "1d 13h"
+fn duration_text(minutes: i64) -> String {
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    let clock = match (hours, minutes) {
+        (0, minutes) if days == 0 => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{}m", hours, minutes),
+    };
+    match days {
+        0 => clock,
+        _ if hours == 0 && minutes == 0 => format!("{}d", days),
+        _ => format!("{}d {}", days, clock),
+    }
+}
+
+pub fn team_status(team: &Team) -> String {
+    let mut out = String::new();
+    let width = team.members.iter().map(|member| member.name.len()).max().unwrap_or(0);
+    let times: Vec<String> = team
+        .members
+        .iter()
+        .map(|member| {
+            format!("{} {} {}",
+                    member.datetime.format("%a, %b %d, %Y"),
+                    clock_time(&member.datetime),
+                    zone_text(&member.datetime, &member.label))
+        })
+        .collect();
+    let time_width = times.iter().map(String::len).max().unwrap_or(0);
+
+    writeln!(out, "\nTEAM").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    for (member, time) in team.members.iter().zip(&times) {
+        let until = member.change.map(|change| duration_text((change - member.datetime).num_minutes()));
+        let status = match (member.working, until) {
+            (true, Some(until)) => format!("working, finishes in {}", until),
+            (false, Some(until)) => format!("{}, starts in {}", member_state(member), until),
+            (_, None) => member_state(member).to_string(),
+        };
+        writeln!(out, "{:<width$}  {:<time_width$}  {}  {}", member.name, time, member.hours, status).unwrap();
+    }
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "{} of {} working now\n",
+             team.members.iter().filter(|member| member.working).count(),
+             team.members.len()).unwrap();
+
+    out
+}
+
+// Slots shown in the text output, the JSON output has all of them
+const MEETING_SLOTS: usize = 5;
+
+// "Mon 10:00-11:00" in the participant's zone
+fn local_span(start: &DateTime<chrono::Utc>, end: &DateTime<chrono::Utc>, participant: &Participant) -> String {
+    let (start, end) = (start.with_timezone(&participant.zone), end.with_timezone(&participant.zone));
+    format!("{} {}-{}", start.format("%a"), clock_time(&start), clock_time(&end))
+}
+
+fn slot_span(slot: &MeetingSlot, participant: &Participant) -> String {
+    local_span(&slot.start, &slot.end, participant)
+}
+
+pub fn meeting(plan: &MeetingPlan) -> String {
+    let mut out = String::new();
+    let width = plan.participants.iter().map(|participant| participant.label.len()).max().unwrap_or(0);
+    // "WIB   Mon 10:00-11:00 WIB (UTC+07:00)", one line per participant
+    let lines = |start: &DateTime<chrono::Utc>, end: &DateTime<chrono::Utc>| -> Vec<String> {
+        plan.participants
+            .iter()
+            .map(|participant| {
+                let local = start.with_timezone(&participant.zone);
+                format!("{:<width$}  {} {} ({})",
+                        participant.label,
+                        local_span(start, end, participant),
+                        local.format("%Z"),
+                        format_offset(&local.offset().fix()))
+            })
+            .collect()
+    };
+
+    writeln!(out, "\nMEETING PLANNER").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "DATE:     {} in {}", plan.date.format("%A, %B %d, %Y"), plan.participants[0].label).unwrap();
+    writeln!(out, "HOURS:    {} local time for everyone", plan.hours).unwrap();
+    writeln!(out, "LENGTH:   {}", duration_text(plan.length.num_minutes())).unwrap();
+    if plan.windows.is_empty() {
+        writeln!(out, "OVERLAP:  none, there is no time when everyone is working").unwrap();
+    }
+    for (start, end) in &plan.windows {
+        for (i, line) in lines(start, end).iter().enumerate() {
+            writeln!(out, "{}{}", if i == 0 { "OVERLAP:  " } else { "          " }, line).unwrap();
+        }
+    }
+    writeln!(out, "{}", RULE).unwrap();
+    for (n, slot) in plan.slots.iter().take(MEETING_SLOTS).enumerate() {
+        for (i, line) in lines(&slot.start, &slot.end).iter().enumerate() {
+            match i {
+                0 => writeln!(out, "{:<10}{}", format!("SLOT {}:", n + 1), line).unwrap(),
+                _ => writeln!(out, "          {}", line).unwrap(),
+            }
+        }
+        writeln!(out, "          {} clear of anyone's start or end of day", duration_text(slot.margin.num_minutes())).unwrap();
+    }
+    if !plan.slots.is_empty() {
+        writeln!(out, "{}", RULE).unwrap();
+    }
+    match plan.slots.len() {
+        0 if plan.windows.is_empty() => writeln!(out, "No slots, try wider --hours or fewer zones\n").unwrap(),
+        0 => writeln!(out, "No slot of {} fits\n", duration_text(plan.length.num_minutes())).unwrap(),
+        1 => writeln!(out, "1 slot\n").unwrap(),
+        count if count <= MEETING_SLOTS => writeln!(out, "{} slots, best first\n", count).unwrap(),
+        count => writeln!(out, "{} of {} slots, best first\n", MEETING_SLOTS, count).unwrap(),
+    }
+
+    out
+}
+
+// "PDT (UTC-07:00)"
+fn offset_name(offset: &ZoneOffset) -> String {
+    format!("{} ({})", offset, format_offset(&offset.fix()))
+}
+
+// "Sunday, March 09, 2025 02:00 PST -> 03:00 PDT"
+fn transition_text(transition: &Transition, time: &str) -> String {
+    format!(
+        "{} {} {} -> {} {}",
+        transition.local_before().format("%A, %B %d, %Y"),
+        transition.local_before().format(time),
+        transition.before,
+        transition.local_after().format(time),
+        transition.after
+    )
+}
+
+// "2025-03-09 02:00 -> 03:00 PST->PDT (UTC-08:00 -> UTC-07:00)", with the
+// date repeated when the clocks jump back into the previous day
+fn transition_line(transition: &Transition, time: &str) -> String {
+    let (before, after) = (transition.local_before(), transition.local_after());
+    let after_pattern = match before.date() == after.date() {
+        true => time.to_string(),
+        false => format!("%Y-%m-%d {}", time),
+    };
+    format!(
+        "{} -> {} {}->{} ({} -> {})",
+        before.format(&format!("%Y-%m-%d {}", time)),
+        after.format(&after_pattern),
+        transition.before,
+        transition.after,
+        format_offset(&transition.before.fix()),
+        format_offset(&transition.after.fix())
+    )
+}
+
+pub fn transitions(list: &TransitionList) -> String {
+    let mut out = String::new();
+    let time = config::active().time_format.pattern();
+
+    writeln!(out, "\nTRANSITIONS").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "ZONE:  {}", list.zone.name()).unwrap();
+    writeln!(out, "RANGE: {} to {}", list.from, list.to).unwrap();
+    for transition in &list.transitions {
+        writeln!(out, "{}", transition_line(transition, time)).unwrap();
+    }
+    if list.transitions.is_empty() {
+        writeln!(out, "No offset changes in this range").unwrap();
+    }
+    writeln!(out, "{}", RULE).unwrap();
+    match list.transitions.len() {
+        1 => writeln!(out, "1 transition\n").unwrap(),
+        count => writeln!(out, "{} transitions\n", count).unwrap(),
+    }
+
+    out
+}
+
+pub fn zone_info(info: &ZoneInfo) -> String {
+    let mut out = String::new();
+    let time = config::active().time_format.pattern();
+
+    writeln!(out, "\nZONE").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "ZONE:      {}", info.now.timezone().name()).unwrap();
+    writeln!(out, "NOW:       {} {} {} ({})",
+             info.now.format("%A, %B %d, %Y"),
+             info.now.format(time),
+             info.now.format("%Z"),
+             format_offset(&info.now.offset().fix())).unwrap();
+    writeln!(out, "STANDARD:  {}", offset_name(&info.standard)).unwrap();
+    match &info.dst {
+        Some(dst) if info.now.offset().is_dst() => writeln!(out, "DST:       {}, in effect now", offset_name(dst)).unwrap(),
+        Some(dst) => writeln!(out, "DST:       {}, not in effect now", offset_name(dst)).unwrap(),
+        None => writeln!(out, "DST:       not observed").unwrap(),
+    }
+    if let Some(previous) = &info.previous {
+        writeln!(out, "PREVIOUS:  {}", transition_text(previous, time)).unwrap();
+    }
+    if let Some(next) = &info.next {
+        writeln!(out, "NEXT:      {}", transition_text(next, time)).unwrap();
+    }
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+pub fn zones(list: &ZoneList) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "\nTIMEZONES").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    for dt in &list.zones {
+        writeln!(out, "{:<32} UTC{}  {}", dt.timezone().name(), dt.format("%:z"), dt.format("%Z")).unwrap();
+    }
+    match &list.search {
+        Some(search) if list.zones.is_empty() => writeln!(out, "No timezone matches '{}'", search).unwrap(),
+        _ => {}
+    }
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "{} zones\n", list.zones.len()).unwrap();
+
+    out
+}
+
+pub fn configuration(config: &Config) -> String {
+    let mut out = String::new();
+    let path = match &config.path {
+        Some(path) if config.loaded => path.display().to_string(),
+        Some(path) => format!("{} (not found, using defaults)", path.display()),
+        None => "(no home directory, using defaults)".to_string(),
+    };
+
+    writeln!(out, "\nCONFIGURATION").unwrap();
+    writeln!(out, "{}", RULE).unwrap();
+    writeln!(out, "FILE:          {}", path).unwrap();
+    writeln!(out, "DEFAULT ZONE:  {}", config.default_zone.as_deref().unwrap_or("(none)")).unwrap();
+    writeln!(out, "OUTPUT:        {}", match config.output {
+        OutputFormat::Text => "text",
+        OutputFormat::Json => "json",
+    }).unwrap();
+    writeln!(out, "TIME FORMAT:   {}", config.time_format.name()).unwrap();
+    writeln!(out, "WEEK START:    {}", weekday_name(config.week_start)).unwrap();
+    writeln!(out, "PREFER:        {}", match config.prefer_regions.is_empty() {
+        true => "(none)".to_string(),
+        false => config.prefer_regions.join(", "),
+    }).unwrap();
+    writeln!(out, "STRICT:        {}", if config.strict { "yes" } else { "no" }).unwrap();
+    writeln!(out, "DST:           {}", config.dst.name()).unwrap();
+    writeln!(out, "MILITARY:      {}", if config.military { "yes" } else { "no" }).unwrap();
+    writeln!(out, "CLOCK ZONES:   {}", config.clock_zones.join(", ")).unwrap();
+    if config.aliases.is_empty() {
+        writeln!(out, "ALIASES:       (none)").unwrap();
+    } else {
+        writeln!(out, "ALIASES:").unwrap();
+        for (alias, zone) in &config.aliases {
+            writeln!(out, "  {} = {}", alias, zone).unwrap();
+        }
+    }
+    if config.team.is_empty() {
+        writeln!(out, "TEAM:          (none)").unwrap();
+    } else {
+        writeln!(out, "TEAM:").unwrap();
+        for member in &config.team {
+            let weekend: Vec<_> = member.weekend.iter().map(|day| weekday_name(*day)).collect();
+            writeln!(out, "  {} = {} {}, weekend {}", member.name, member.zone, member.hours, weekend.join(", ")).unwrap();
+        }
+    }
+    writeln!(out, "{}\n", RULE).unwrap();
+
+    out
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
@@ -0,0 +1,256 @@
+// Interactive prompt. Every line uses the same grammar as the command line
+// (`tz 4:00 UTC+7 to WIB`, `future 30d`), plus `_` for the previous result:
+//
+//   _            show it again
+//   _ + 3d       shift it (d, h, m, w, s)
+//   _ to JST     convert it to another zone
+
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::NaiveDateTime;
+use clap::error::ErrorKind;
+use clap::CommandFactory;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{Context, Editor, Helper, Highlighter, Hinter, Validator};
+
+use crate::cli::{self, Cli};
+use crate::clock::Clock;
+use crate::completions;
+use crate::{parse_duration, TimecalcError, Zone};
+use crate::render::{self, OutputFormat, Report};
+
+const PROMPT: &str = "timecalc> ";
+
+// The previous result. Conversions keep their zone so `_ to JST` works,
+// date calculations are plain calendar dates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    pub datetime: NaiveDateTime,
+    // The resolved zone, and the label it was shown with. The label is only
+    // for display: "Germany (DE)" does not parse back into a zone.
+    pub zone: Option<(Zone, String)>,
+}
+
+impl Value {
+    fn from_report(report: &Report) -> Option<Value> {
+        let (datetime, zone) = match report {
+            Report::Date(calc) => (calc.date.date_naive().and_hms_opt(0, 0, 0)?, None),
+            Report::Conversion(conv) => (conv.to.naive_local(), Some((conv.to.timezone(), conv.to_label.clone()))),
+            Report::Remaining(rem) => (rem.end.and_hms_opt(0, 0, 0)?, None),
+            Report::Day(info) => (info.date.and_hms_opt(0, 0, 0)?, None),
+            Report::Where(_)
+            | Report::Clock(_)
+            | Report::Team(_)
+            | Report::Meeting(_)
+            | Report::ZoneInfo(_)
+            | Report::Transitions(_)
+            | Report::Zones(_)
+            | Report::Config(_)
+            | Report::Script(_) => return None,
+        };
+
+        Some(Value { datetime, zone })
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.zone {
+            Some((_, label)) => write!(f, "{} {}", self.datetime.format("%A, %B %d, %Y %H:%M"), label),
+            None => write!(f, "{}", self.datetime.format("%A, %B %d, %Y")),
+        }
+    }
+}
+
+pub struct Session<'a> {
+    clock: &'a dyn Clock,
+    format: OutputFormat,
+    last: Option<Value>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(clock: &'a dyn Clock, format: OutputFormat) -> Self {
+        Session { clock, format, last: None }
+    }
+
+    pub fn last(&self) -> Option<&Value> {
+        self.last.as_ref()
+    }
+
+    // Evaluate one line, returning what to print on stdout or on stderr
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix('_') {
+            return self.eval_reference(rest.trim());
+        }
+
+        let cli = match cli::parse_line(line) {
+            Ok(cli) => cli,
+            Err(err) if matches!(err.kind(), ErrorKind::DisplayHelp | ErrorKind::DisplayVersion) => {
+                return Ok(err.to_string())
+            }
+            Err(err) => return Err(err.to_string()),
+        };
+        let (report, format) = cli
+            .execute_line(self.clock, self.format)
+            .map_err(|err| render::error_text(&err))?;
+
+        if let Some(value) = Value::from_report(&report) {
+            self.last = Some(value);
+        }
+        let mut output = String::new();
+        if format == OutputFormat::Text {
+            for warning in render::warnings(&report) {
+                output.push_str(&format!("WARNING: {}\n", warning));
+            }
+        }
+        output.push_str(&render::render(&report, format));
+        Ok(output)
+    }
+
+    fn eval_reference(&mut self, rest: &str) -> Result<String, String> {
+        let last = self.last.clone().ok_or("ERROR: There is no previous result yet")?;
+
+        if rest.is_empty() {
+            return Ok(format!("{}\n", last));
+        }
+
+        if rest.to_lowercase().starts_with("to ") {
+            let target = &rest[3..];
+            let (zone, _) = last.zone.as_ref().ok_or("ERROR: The previous result has no timezone")?;
+            let line = format!("tz {} {} to {}", last.datetime.format("%Y-%m-%d %H:%M"), zone.name(), target.trim());
+            return self.eval(&line);
+        }
+
+        let (sign, amount) = match rest.chars().next() {
+            Some('+') => (1, &rest[1..]),
+            Some('-') => (-1, &rest[1..]),
+            _ => return Err("ERROR: Use '_', '_ + 3d', '_ - 2h' or '_ to JST'".to_string()),
+        };
+        let duration = parse_duration(amount)
+            .ok_or_else(|| format!("ERROR: Could not parse duration: '{}'", amount.trim()))?;
+
+        let datetime = duration
+            .checked_mul(sign)
+            .and_then(|duration| last.datetime.checked_add_signed(duration))
+            .ok_or_else(|| format!("ERROR: '_ {}' is out of range", rest))?;
+
+        let value = Value { datetime, zone: last.zone };
+        self.last = Some(value.clone());
+        Ok(format!("{}\n", value))
+    }
+}
+
+#[derive(Helper, Hinter, Highlighter, Validator)]
+struct ReplHelper {
+    commands: Vec<String>,
+    zones: Vec<String>,
+}
+
+impl ReplHelper {
+    fn new() -> Self {
+        let commands = Cli::command()
+            .get_subcommands()
+            .flat_map(|sub| std::iter::once(sub.get_name()).chain(sub.get_visible_aliases()))
+            .map(str::to_string)
+            .chain(["exit".to_string(), "quit".to_string()])
+            .collect();
+
+        ReplHelper { commands, zones: completions::zone_names() }
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    // Command names for the first word, zone names and "to" after that
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos].rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = line[start..pos].to_lowercase();
+
+        let words: Vec<String> = if start == 0 {
+            self.commands.clone()
+        } else {
+            std::iter::once("to".to_string()).chain(self.zones.iter().cloned()).collect()
+        };
+
+        let matches = words
+            .into_iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&word))
+            .collect();
+        Ok((start, matches))
+    }
+}
+
+// TIMECALC_HISTORY, or timecalc/history.txt in the platform data directory
+pub fn history_path() -> Option<PathBuf> {
+    match std::env::var_os("TIMECALC_HISTORY") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => dirs::data_local_dir().map(|dir| dir.join("timecalc").join("history.txt")),
+    }
+}
+
+// Fails only when the terminal cannot be read. A history file that cannot be
+// saved is reported as a warning, the session itself went fine.
+pub fn run(clock: &dyn Clock, format: OutputFormat) -> Result<(), TimecalcError> {
+    let terminal_failed = |err: ReadlineError| TimecalcError::ReadFailed {
+        path: "terminal".to_string(),
+        reason: err.to_string(),
+    };
+    let mut editor = Editor::<ReplHelper, DefaultHistory>::new().map_err(terminal_failed)?;
+    editor.set_helper(Some(ReplHelper::new()));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        // A missing history file just means this is the first session
+        let _ = editor.load_history(path);
+    }
+
+    let mut session = Session::new(clock, format);
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(terminal_failed(err)),
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line).map_err(terminal_failed)?;
+        if line == "exit" || line == "quit" {
+            break;
+        }
+
+        match session.eval(line) {
+            Ok(output) => print!("{}", output),
+            Err(message) => eprint!("{}", ensure_newline(message)),
+        }
+    }
+
+    if let Some(path) = &history {
+        let saved = match path.parent() {
+            Some(dir) => fs::create_dir_all(dir).map_err(ReadlineError::from),
+            None => Ok(()),
+        }
+        .and_then(|_| editor.save_history(path));
+        if let Err(err) = saved {
+            eprintln!("WARNING: Could not save history to '{}': {}", path.display(), err);
+        }
+    }
+
+    Ok(())
+}
+
+fn ensure_newline(mut text: String) -> String {
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,268 @@
+// A timezone as the user named it: an IANA zone with its DST rules, or a
+// fixed UTC offset ("UTC+5:30", "GMT-3", "+0545") that never changes.
+// Implements chrono's TimeZone so conversions work the same for both.
+
+use std::sync::LazyLock;
+use std::{env, fmt, fs};
+
+use chrono::{DateTime, Duration, FixedOffset, Local, MappedLocalTime, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TzOffset};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoneOffset {
+    Named(TzOffset),
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    // "UTC+7", "UTC+07:00", "GMT-3", "+0545", "-03:30". These follow the
+    // everyday sign convention, unlike POSIX "Etc/GMT-7" (which is UTC+7 and
+    // goes through the IANA table like any other name).
+    pub fn parse_offset(input: &str) -> Option<Zone> {
+        // Compiled once, every zone name the resolver sees comes through here
+        static RE: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"(?i)^(?:UTC|GMT)?\s*([+-])(\d{1,2})(?::?(\d{2}))?$").expect("valid regex"));
+        let caps = RE.captures(input.trim())?;
+
+        let hours: i32 = caps[2].parse().ok()?;
+        let minutes: i32 = caps.get(3).map_or(Some(0), |m| m.as_str().parse().ok())?;
+        if hours > 14 || minutes >= 60 {
+            return None;
+        }
+
+        let seconds = (hours * 3600 + minutes * 60) * if &caps[1] == "-" { -1 } else { 1 };
+        FixedOffset::east_opt(seconds).map(Zone::Fixed)
+    }
+
+    // The system's zone, from TZ or else /etc/localtime or /etc/timezone. When
+    // none of them names an IANA zone, the offset the system uses right now.
+    pub fn local() -> Zone {
+        system_tz()
+            .map(Zone::Named)
+            .unwrap_or_else(|| Zone::Fixed(Local::now().offset().fix()))
+    }
+
+    // The same zone under its canonical IANA name: "US/Pacific" is
+    // America/Los_Angeles
+    pub fn canonical(self) -> Zone {
+        match self {
+            Zone::Named(tz) => Zone::Named(crate::links::canonical(tz)),
+            Zone::Fixed(_) => self,
+        }
+    }
+
+    // IANA name, or the offset spelled out as "UTC+05:45"
+    pub fn name(&self) -> String {
+        match self {
+            Zone::Named(tz) => tz.name().to_string(),
+            Zone::Fixed(offset) => format_offset(offset),
+        }
+    }
+}
+
+// A change of UTC offset or abbreviation, such as the start of DST
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub at: DateTime<Utc>,
+    pub before: ZoneOffset,
+    pub after: ZoneOffset,
+}
+
+impl Transition {
+    // Wall clock time just before and at the change: 02:00 -> 03:00
+    pub fn local_before(&self) -> NaiveDateTime {
+        self.at.naive_utc() + Duration::seconds(i64::from(self.before.fix().local_minus_utc()))
+    }
+
+    pub fn local_after(&self) -> NaiveDateTime {
+        self.at.naive_utc() + Duration::seconds(i64::from(self.after.fix().local_minus_utc()))
+    }
+}
+
+// How far transitions are looked for. Zones without DST can go decades
+// without one, tzdata has nothing before 1900.
+const SEARCH_YEARS: i64 = 150;
+
+impl Zone {
+    // The first transition after `from`, if any within SEARCH_YEARS
+    pub fn next_transition(&self, from: DateTime<Utc>) -> Option<Transition> {
+        self.find_transition(from, Duration::days(1), SEARCH_YEARS * 366)
+    }
+
+    // The last transition at or before `from`
+    pub fn previous_transition(&self, from: DateTime<Utc>) -> Option<Transition> {
+        self.find_transition(from, Duration::days(-1), SEARCH_YEARS * 366)
+    }
+
+    // Every transition from `from` up to (not including) `to`
+    pub fn transitions(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Transition> {
+        let mut transitions = Vec::new();
+        let mut current = from;
+        while let Some(transition) = self.find_transition(current, Duration::days(1), (to - current).num_days() + 1) {
+            if transition.at >= to {
+                break;
+            }
+            current = transition.at;
+            transitions.push(transition);
+        }
+        transitions
+    }
+
+    fn offset_at(&self, timestamp: i64) -> ZoneOffset {
+        let at = DateTime::from_timestamp(timestamp, 0).unwrap_or_default();
+        self.offset_from_utc_datetime(&at.naive_utc())
+    }
+
+    // Step a day at a time until the offset changes, then bisect down to the
+    // second it changed at. Two changes within one day count as one.
+    fn find_transition(&self, from: DateTime<Utc>, step: Duration, days: i64) -> Option<Transition> {
+        if let Zone::Fixed(_) = self {
+            return None;
+        }
+
+        let step = step.num_seconds();
+        let mut current = from.timestamp();
+        for _ in 0..days {
+            let next = current + step;
+            if self.offset_at(next) != self.offset_at(current) {
+                let (mut before, mut after) = (current.min(next), current.max(next));
+                let old = self.offset_at(before);
+                while after - before > 1 {
+                    let middle = before + (after - before) / 2;
+                    if self.offset_at(middle) == old {
+                        before = middle;
+                    } else {
+                        after = middle;
+                    }
+                }
+                return Some(Transition {
+                    at: DateTime::from_timestamp(after, 0)?,
+                    before: old,
+                    after: self.offset_at(after),
+                });
+            }
+            current = next;
+        }
+        None
+    }
+}
+
+impl From<Tz> for Zone {
+    fn from(tz: Tz) -> Self {
+        Zone::Named(tz)
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+fn system_tz() -> Option<Tz> {
+    // "Asia/Jakarta", ":Asia/Jakarta" or a path into the zoneinfo directory
+    let zoneinfo_name = |path: &str| match path.find("zoneinfo/") {
+        Some(pos) => path[pos + "zoneinfo/".len()..].to_string(),
+        None => path.trim_start_matches(':').to_string(),
+    };
+
+    // A TZ that is set wins, even when it is a POSIX rule rather than a name
+    if let Some(tz) = env::var("TZ").ok().filter(|tz| !tz.is_empty()) {
+        return zoneinfo_name(&tz).parse().ok();
+    }
+    fs::read_link("/etc/localtime")
+        .ok()
+        .and_then(|path| zoneinfo_name(&path.to_string_lossy()).parse().ok())
+        .or_else(|| fs::read_to_string("/etc/timezone").ok()?.trim().parse().ok())
+}
+
+// "UTC+07:00", "UTC-03:30"
+pub fn format_offset(offset: &FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+impl ZoneOffset {
+    // "PDT", None for a fixed offset or a zone that has no abbreviation
+    pub fn abbreviation(&self) -> Option<&str> {
+        match self {
+            ZoneOffset::Named(offset) => offset.abbreviation(),
+            ZoneOffset::Fixed(_) => None,
+        }
+    }
+
+    // Whether this is a daylight saving offset; fixed offsets never are
+    pub fn is_dst(&self) -> bool {
+        match self {
+            ZoneOffset::Named(offset) => !offset.dst_offset().is_zero(),
+            ZoneOffset::Fixed(_) => false,
+        }
+    }
+}
+
+impl Offset for ZoneOffset {
+    fn fix(&self) -> FixedOffset {
+        match self {
+            ZoneOffset::Named(offset) => offset.fix(),
+            ZoneOffset::Fixed(offset) => *offset,
+        }
+    }
+}
+
+// What %Z prints: the zone abbreviation, or the offset for a fixed zone
+impl fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.abbreviation() {
+            Some(abbreviation) => write!(f, "{}", abbreviation),
+            None => write!(f, "{}", format_offset(&self.fix())),
+        }
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = ZoneOffset;
+
+    fn from_offset(offset: &ZoneOffset) -> Self {
+        match offset {
+            ZoneOffset::Named(offset) => Zone::Named(Tz::from_offset(offset)),
+            ZoneOffset::Fixed(offset) => Zone::Fixed(*offset),
+        }
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            Zone::Named(tz) => tz.offset_from_local_date(local).map(ZoneOffset::Named),
+            Zone::Fixed(offset) => offset.offset_from_local_date(local).map(ZoneOffset::Fixed),
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            Zone::Named(tz) => tz.offset_from_local_datetime(local).map(ZoneOffset::Named),
+            Zone::Fixed(offset) => offset.offset_from_local_datetime(local).map(ZoneOffset::Fixed),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+        match self {
+            Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_date(utc)),
+            Zone::Fixed(offset) => ZoneOffset::Fixed(offset.offset_from_utc_date(utc)),
+        }
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+        match self {
+            Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_datetime(utc)),
+            Zone::Fixed(offset) => ZoneOffset::Fixed(offset.offset_from_utc_datetime(utc)),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use timecalc::batch;
+use timecalc::render::OutputFormat;
+use timecalc::*;
+
+fn clock() -> FixedClock {
+    FixedClock::parse("2025-02-27 09:30").unwrap()
+}
+
+fn run(input: &str, format: OutputFormat) -> (String, String, batch::BatchSummary) {
+    let mut out = Vec::new();
+    let mut err = Vec::new();
+    let summary = batch::run(input.as_bytes(), &clock(), format, &mut out, &mut err).unwrap();
+    (String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap(), summary)
+}
+
+#[test]
+fn test_batch_one_result_per_line() {
+    let (out, err, summary) = run(
+        "tz 2025-10-09 04:00 UTC+8 to WIB\npast 2 days\nremaining month\n",
+        OutputFormat::Text,
+    );
+
+    assert_eq!(
+        out,
+        "2025-10-09 04:00 UTC+8 -> 2025-10-09 03:00 WIB\n\
+         2025-02-27 - 2 days = 2025-02-25 (Tuesday)\n\
+         2025-02-27 month: 1 days remaining until 2025-02-28, 27 days passed\n"
+    );
+    assert!(err.is_empty());
+    assert_eq!(summary, batch::BatchSummary { total: 3, failed: vec![] });
+}
+
+#[test]
+fn test_batch_skips_blank_lines_and_comments() {
+    let (out, _, summary) = run("\n# header\n   \nday 2025-12-25\n", OutputFormat::Text);
+
+    assert_eq!(out, "2025-12-25 Thursday\n");
+    assert_eq!(summary.total, 1);
+}
+
+#[test]
+fn test_batch_keeps_going_after_errors() {
+    let (out, err, summary) = run("future abc\nday 2025-12-25\nday 2025-13-45\n", OutputFormat::Text);
+
+    assert_eq!(out, "2025-12-25 Thursday\n");
+    assert!(err.contains("line 1: ERROR: Could not parse days: 'abc'"));
+    assert!(err.contains("line 3: ERROR: Invalid date format: '2025-13-45'"));
+    assert_eq!(summary.failed, vec![1, 3]);
+}
+
+#[test]
+fn test_batch_line_output_flag() {
+    let (out, _, _) = run("day 2025-12-25 --output json\nday 2025-12-26\n", OutputFormat::Text);
+
+    assert_eq!(
+        out,
+        "{\"command\":\"day\",\"date\":\"2025-12-25\",\"weekday\":\"Thursday\"}\n2025-12-26 Friday\n"
+    );
+}
+
+#[test]
+fn test_batch_dst_flag_applies_to_its_line_only() {
+    let (out, err, summary) = run(
+        "tz 2025-03-09 02:30 PST to UTC --dst reject\ntz 2025-03-09 02:30 PST to UTC\n",
+        OutputFormat::Text,
+    );
+
+    assert!(err.starts_with("line 1: ERROR: 02:30 does not exist in America/Los_Angeles"));
+    assert!(out.starts_with("2025-03-09 01:30 PST -> 2025-03-09 09:30 UTC (02:30 does not exist"));
+    assert_eq!(summary.failed, vec![1]);
+}
+
+#[test]
+fn test_batch_several_destinations_on_one_line() {
+    let (out, _, _) = run("tz 2025-10-09 04:00 UTC+8 to WIB, JST\n", OutputFormat::Text);
+    assert_eq!(out, "2025-10-09 04:00 UTC+8 -> 2025-10-09 03:00 WIB, 2025-10-09 05:00 JST\n");
+}
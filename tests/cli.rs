@@ -3,7 +3,9 @@ use predicates::prelude::*;
 use escargot::CargoBuild;
 use std::process::Command;
 
-// Helper function to create a command for the binary
+// Helper function to create a command for the binary. It never reads the
+// developer's own config file or pinned clock; tests that need a config
+// pass their own through config_file().
 fn cmd() -> Command {
         let bin_path = CargoBuild::new()
         .bin("timecalc") // The name of your binary
@@ -12,7 +14,12 @@ fn cmd() -> Command {
         .path()
         .to_path_buf();
 
-    Command::new(bin_path)
+    let mut command = Command::new(bin_path);
+    command
+        .env("TIMECALC_CONFIG", "/nonexistent/timecalc/config.toml")
+        .env_remove("TIMECALC_NOW")
+        .env_remove("XDG_CONFIG_HOME");
+    command
 }
 
 // ===================================
@@ -41,8 +48,9 @@ fn test_invalid_command() {
     cmd()
         .arg("foobar")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Unknown command: foobar"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("unrecognized subcommand 'foobar'"));
 }
 
 // ===================================
@@ -64,8 +72,9 @@ fn test_future_command_error_no_days() {
     cmd()
         .arg("future")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Please specify number of days"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("the following required arguments were not provided"));
 }
 
 #[test]
@@ -74,8 +83,9 @@ fn test_future_command_error_invalid_days() {
         .arg("future")
         .arg("foo")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Could not parse days"));
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("ERROR: Could not parse days"));
 }
 
 // ===================================
@@ -97,8 +107,9 @@ fn test_past_command_error_no_days() {
     cmd()
         .arg("past")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Please specify number of days"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("the following required arguments were not provided"));
 }
 
 #[test]
@@ -107,8 +118,9 @@ fn test_past_command_error_invalid_days() {
         .arg("past")
         .arg("foo")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Could not parse days"));
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("ERROR: Could not parse days"));
 }
 
 // ===================================
@@ -132,8 +144,9 @@ fn test_day_command_error() {
         .arg("day")
         .arg("invalid-date")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Invalid date format"));
+        .failure()
+        .code(7)
+        .stderr(predicate::str::contains("ERROR: Invalid date format"));
 }
 
 #[test]
@@ -141,8 +154,9 @@ fn test_day_command_no_args() {
     cmd()
         .arg("day")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Please provide a date"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("the following required arguments were not provided"));
 }
 
 // ===================================
@@ -175,8 +189,9 @@ fn test_remaining_error() {
         .arg("left")
         .arg("foobar")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Use 'month' or 'year'"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'foobar'"));
 }
 
 #[test]
@@ -184,8 +199,9 @@ fn test_remaining_no_args() {
     cmd()
         .arg("remaining")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Specify 'month' or 'year'"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("the following required arguments were not provided"));
 }
 
 // ===================================
@@ -215,8 +231,9 @@ fn test_tz_convert_error_no_to() {
         .arg("from")
         .arg("WIB")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Missing 'to' keyword"));
+        .failure()
+        .code(6)
+        .stderr(predicate::str::contains("ERROR: Missing 'to' keyword"));
 }
 
 #[test]
@@ -228,8 +245,9 @@ fn test_tz_convert_error_invalid_datetime() {
         .arg("to")
         .arg("WIB")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Could not parse date/time"));
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("ERROR: Could not parse date/time"));
 }
 
 #[test]
@@ -241,8 +259,9 @@ fn test_tz_convert_error_invalid_tz() {
         .arg("to")
         .arg("WIB")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Unsupported timezone"));
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("ERROR: Unsupported timezone"));
 }
 
 #[test]
@@ -251,8 +270,9 @@ fn test_tz_convert_insufficient_args() {
         .arg("tz")
         .arg("04:00AM")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("ERROR: Invalid format"));
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("ERROR: Invalid format"));
 }
 
 #[test]
@@ -433,4 +453,873 @@ fn test_tz_jst_to_wib() {
         .assert()
         .success()
         .stdout(predicate::str::contains("TIMEZONE CONVERSION"));
-}
\ No newline at end of file
+}
+// ===================================
+// Tests for per-command help and suggestions
+// ===================================
+
+#[test]
+fn test_subcommand_help() {
+    cmd()
+        .args(["tz", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: timecalc convert"))
+        .stdout(predicate::str::contains("timecalc tz October 9, 2025 at 04:00AM UTC+8 to WIB"));
+}
+
+#[test]
+fn test_subcommand_help_after_expression() {
+    cmd()
+        .args(["tz", "10:00", "PST", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: timecalc convert"));
+}
+
+#[test]
+fn test_help_for_command() {
+    cmd()
+        .args(["help", "remaining"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: timecalc remaining"));
+}
+
+#[test]
+fn test_did_you_mean() {
+    cmd()
+        .arg("futur")
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("a similar subcommand exists: 'future'"));
+}
+
+// ===================================
+// Tests for --now / TIMECALC_NOW
+// ===================================
+
+#[test]
+fn test_now_flag_remaining_month() {
+    cmd()
+        .args(["--now", "2025-02-27", "remaining", "month"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("END OF MONTH:    February 28, 2025"))
+        .stdout(predicate::str::contains("DAYS REMAINING:  1 days"));
+}
+
+#[test]
+fn test_now_flag_equals_form() {
+    cmd()
+        .args(["future", "3", "--now=2025-02-27"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AFTER 3 DAYS: Sunday, March 02, 2025"));
+}
+
+#[test]
+fn test_now_env_var() {
+    cmd()
+        .env("TIMECALC_NOW", "2024-12-31 23:00")
+        .args(["left", "year"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DAYS REMAINING:  0 days"))
+        .stdout(predicate::str::contains("DAYS PASSED:     366 days"));
+}
+
+#[test]
+fn test_now_flag_invalid() {
+    cmd()
+        .args(["--now", "someday", "remaining", "month"])
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("ERROR: Could not parse date/time: 'someday'"));
+}
+
+#[test]
+fn test_global_flags_after_expression() {
+    cmd()
+        .args(["tz", "2025-10-09", "04:00", "UTC+8", "to", "WIB", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"datetime\":\"2025-10-09T03:00:00+07:00\""));
+}
+
+// ===================================
+// Tests for --output json
+// ===================================
+
+fn json_stdout(args: &[&str]) -> serde_json::Value {
+    let output = cmd().args(args).output().unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_json_future() {
+    let value = json_stdout(&["--output", "json", "--now", "2025-02-27", "future", "3"]);
+    assert_eq!(value["command"], "future");
+    assert_eq!(value["days"], 3);
+    assert_eq!(value["weekday"], "Sunday");
+    assert!(value["date"].as_str().unwrap().starts_with("2025-03-02T00:00:00"));
+}
+
+#[test]
+fn test_json_convert() {
+    let value = json_stdout(&["--output=json", "tz", "2025-10-09", "04:00", "UTC+8", "to", "WIB"]);
+    assert_eq!(value["command"], "convert");
+    assert_eq!(value["from"]["datetime"], "2025-10-09T04:00:00+08:00");
+    // UTC+8 is a fixed offset, not a region
+    assert_eq!(value["from"]["zone"], "UTC+08:00");
+    assert_eq!(value["to"]["datetime"], "2025-10-09T03:00:00+07:00");
+    assert_eq!(value["to"]["zone"], "Asia/Jakarta");
+    assert_eq!(value["to"]["offset"], "+07:00");
+    assert_eq!(value["to"]["abbreviation"], "WIB");
+}
+
+#[test]
+fn test_json_remaining() {
+    let value = json_stdout(&["--now", "2025-02-27", "--output", "json", "remaining", "month"]);
+    assert_eq!(value["period"], "month");
+    assert_eq!(value["end"], "2025-02-28");
+    assert_eq!(value["days_remaining"], 1);
+    assert_eq!(value["days_passed"], 27);
+}
+
+#[test]
+fn test_json_day() {
+    let value = json_stdout(&["--output", "json", "day", "2025-12-25"]);
+    assert_eq!(value["date"], "2025-12-25");
+    assert_eq!(value["weekday"], "Thursday");
+}
+
+#[test]
+fn test_json_error() {
+    let output = cmd().args(["--output", "json", "day", "nope"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(7));
+    let value: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(value["error"]["kind"], "invalid_date");
+    assert_eq!(value["error"]["code"], 7);
+}
+
+#[test]
+fn test_output_format_invalid() {
+    cmd()
+        .args(["--output", "xml", "day", "2025-12-25"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'xml'"));
+}
+
+// ===================================
+// Tests for shell completions
+// ===================================
+
+#[test]
+fn test_completions_command() {
+    cmd()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("_clap_complete_timecalc"));
+}
+
+#[test]
+fn test_completions_unknown_shell() {
+    cmd()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+fn test_completion_callback_zones() {
+    cmd()
+        .env("COMPLETE", "fish")
+        .args(["--", "timecalc", "tz", "10:00", "sg"])
+        .assert()
+        .success()
+        // The alias first, then the country code for Singapore
+        .stdout("SGT\nSG\n");
+}
+
+#[test]
+fn test_completion_callback_remaining() {
+    cmd()
+        .env("COMPLETE", "fish")
+        .args(["--", "timecalc", "remaining", ""])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("month\nyear\n"));
+}
+
+// ===================================
+// Tests for the REPL
+// ===================================
+
+#[test]
+fn test_repl_from_stdin() {
+    let history = std::env::temp_dir().join(format!("timecalc-history-{}.txt", std::process::id()));
+
+    assert_cmd::Command::from_std(cmd())
+        .arg("repl")
+        .env("TIMECALC_HISTORY", &history)
+        .write_stdin("tz 2025-10-09 04:00 UTC+8 to WIB\n_ to JST\n_ + 1d\nexit\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 05:00 JST"))
+        .stdout(predicate::str::contains("Friday, October 10, 2025 05:00 JST"));
+
+    let saved = std::fs::read_to_string(&history).unwrap();
+    assert!(saved.contains("_ to JST"));
+    std::fs::remove_file(&history).unwrap();
+}
+
+// ===================================
+// Tests for batch mode
+// ===================================
+
+#[test]
+fn test_batch_from_file() {
+    let file = std::env::temp_dir().join(format!("timecalc-batch-{}.txt", std::process::id()));
+    std::fs::write(&file, "# events\ntz 2025-10-09 04:00 UTC+8 to WIB\n\nday 2025-12-25\nfuture 3\n").unwrap();
+
+    cmd()
+        .args(["batch", file.to_str().unwrap(), "--now", "2025-02-27"])
+        .assert()
+        .success()
+        .stdout("2025-10-09 04:00 UTC+8 -> 2025-10-09 03:00 WIB\n2025-12-25 Thursday\n2025-02-27 + 3 days = 2025-03-02 (Sunday)\n");
+
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[test]
+fn test_batch_from_stdin_reports_failing_lines() {
+    assert_cmd::Command::from_std(cmd())
+        .arg("batch")
+        .write_stdin("day 2025-12-25\ntz 10:00 MARS to WIB\nday 2025-01-01\n")
+        .assert()
+        .failure()
+        .code(9)
+        .stdout("2025-12-25 Thursday\n2025-01-01 Wednesday\n")
+        .stderr(predicate::str::contains("line 2: ERROR: Unsupported timezone: 'MARS'"))
+        .stderr(predicate::str::contains("ERROR: 1 of 3 lines failed"));
+}
+
+#[test]
+fn test_batch_json_lines() {
+    assert_cmd::Command::from_std(cmd())
+        .args(["batch", "-", "--output", "json"])
+        .write_stdin("day 2025-12-25\nbogus\n")
+        .assert()
+        .failure()
+        .code(9)
+        .stdout("{\"command\":\"day\",\"date\":\"2025-12-25\",\"weekday\":\"Thursday\"}\n")
+        .stderr(predicate::str::contains("{\"error\":\"error: unrecognized subcommand 'bogus'\",\"line\":2}"));
+}
+
+#[test]
+fn test_batch_missing_file() {
+    cmd()
+        .args(["batch", "/nonexistent/timecalc-batch.txt"])
+        .assert()
+        .failure()
+        .code(8)
+        .stderr(predicate::str::contains("ERROR: Could not read '/nonexistent/timecalc-batch.txt'"));
+}
+
+// ===================================
+// Tests for the configuration file
+// ===================================
+
+fn config_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("timecalc-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_config_show() {
+    let path = config_file("show", "default_zone = \"WIB\"\n\n[aliases]\noffice = \"Asia/Jakarta\"\n");
+
+    cmd()
+        .args(["config", "show"])
+        .env("TIMECALC_CONFIG", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DEFAULT ZONE:  WIB"))
+        .stdout(predicate::str::contains("TIME FORMAT:   24h"))
+        .stdout(predicate::str::contains("office = Asia/Jakarta"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_config_default_zone_and_output() {
+    let path = config_file("defaults", "default_zone = \"office\"\noutput = \"json\"\n\n[aliases]\noffice = \"Asia/Jakarta\"\n");
+
+    cmd()
+        .args(["tz", "2025-10-09", "10:00", "PST"])
+        .env("TIMECALC_CONFIG", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"datetime\":\"2025-10-10T00:00:00+07:00\""))
+        .stdout(predicate::str::contains("\"label\":\"OFFICE\""));
+
+    // --output on the command line wins over the file
+    cmd()
+        .args(["day", "2025-12-25", "--output", "text"])
+        .env("TIMECALC_CONFIG", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DAY:  Thursday"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_config_invalid() {
+    let path = config_file("invalid", "time_format = \"25h\"\n");
+
+    cmd()
+        .args(["day", "2025-12-25"])
+        .env("TIMECALC_CONFIG", &path)
+        .assert()
+        .failure()
+        .code(10)
+        .stderr(predicate::str::contains("ERROR: Invalid configuration in"))
+        .stderr(predicate::str::contains("time_format: expected '12h' or '24h', got '25h'"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+// ===================================
+// Tests for IANA zones
+// ===================================
+
+#[test]
+fn test_zones_search() {
+    cmd()
+        .args(["zones", "--search", "jakarta", "--now", "2025-01-15"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Asia/Jakarta                     UTC+07:00  WIB"))
+        .stdout(predicate::str::contains("1 zones"));
+}
+
+#[test]
+fn test_zones_json() {
+    cmd()
+        .args(["zones", "-s", "new york", "--now", "2025-07-01", "--output", "json"])
+        .assert()
+        .success()
+        .stdout("{\"command\":\"zones\",\"search\":\"new york\",\"zones\":[{\"abbreviation\":\"EDT\",\"offset\":\"-04:00\",\"zone\":\"America/New_York\"}]}\n");
+}
+
+#[test]
+fn test_convert_iana_zone() {
+    cmd()
+        .args(["tz", "2025-10-09", "10:00", "america/sao paulo", "to", "Europe/Berlin"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 15:00 CEST (UTC+02:00) [Europe/Berlin]  +5h"));
+}
+
+#[test]
+fn test_convert_unknown_zone_suggestions() {
+    cmd()
+        .args(["tz", "10:00", "Europe/Berln", "to", "WIB"])
+        .assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("Did you mean: Europe/Berlin"));
+}
+
+// ===================================
+// Tests for fixed UTC offsets
+// ===================================
+
+#[test]
+fn test_convert_fixed_offset_ignores_dst() {
+    // UTC-8 stays UTC-8 in July, it is not America/Los_Angeles
+    cmd()
+        .args(["tz", "2025-07-01", "12:00", "UTC-8", "to", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FROM: Tuesday, July 01, 2025 12:00 UTC-8 (UTC-08:00)"))
+        .stdout(predicate::str::contains("TO:   Tuesday, July 01, 2025 20:00 UTC (UTC+00:00)"));
+}
+
+#[test]
+fn test_convert_half_and_quarter_hour_offsets() {
+    cmd()
+        .args(["tz", "2025-10-09", "12:00", "UTC+5:30", "to", "+0545"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 12:15 +0545 (UTC+05:45)"));
+
+    cmd()
+        .args(["tz", "2025-10-09", "12:00", "-03:30", "to", "GMT-3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 12:30 GMT-3 (UTC-03:00)"));
+}
+
+#[test]
+fn test_convert_etc_gmt_sign_inversion() {
+    // POSIX names invert the sign: Etc/GMT-7 is seven hours ahead of UTC
+    cmd()
+        .args(["tz", "2025-10-09", "12:00", "UTC", "to", "Etc/GMT-7"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 19:00 Etc/GMT-7 (UTC+07:00)"));
+}
+
+// ===================================
+// Tests for cities and countries
+// ===================================
+
+#[test]
+fn test_where_city() {
+    cmd()
+        .args(["where", "Bandung", "--now", "2025-07-01T12:00:00Z"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PLACE: Bandung"))
+        .stdout(predicate::str::contains("ZONE:  Asia/Jakarta WIB (UTC+07:00)"))
+        .stdout(predicate::str::contains("TIME:  Tuesday, July 01, 2025 19:00"));
+}
+
+#[test]
+fn test_where_country_with_several_zones() {
+    cmd()
+        .args(["where", "ID", "--now", "2025-07-01T12:00:00Z"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PLACE: Indonesia (ID)"))
+        .stdout(predicate::str::contains("Asia/Makassar WITA (UTC+08:00)"))
+        .stdout(predicate::str::contains("Asia/Jayapura WIT (UTC+09:00)"));
+}
+
+#[test]
+fn test_where_unknown_place() {
+    cmd()
+        .args(["where", "Tokio"])
+        .assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("ERROR: Unknown city or country: 'Tokio'"))
+        .stderr(predicate::str::contains("Did you mean: Tokyo?"));
+}
+
+#[test]
+fn test_convert_ambiguous_country() {
+    cmd()
+        .args(["tz", "10:00", "ID", "to", "JST"])
+        .assert()
+        .failure()
+        .code(11)
+        .stderr(predicate::str::contains("ERROR: 'ID' matches 3 timezones"))
+        .stderr(predicate::str::contains("Use one of: Asia/Jakarta, Asia/Makassar, Asia/Jayapura"));
+}
+
+// ===================================
+// Tests for ambiguous abbreviations
+// ===================================
+
+#[test]
+fn test_convert_ambiguous_abbreviation_warns() {
+    cmd()
+        .args(["tz", "2025-01-15", "10:00", "CST", "to", "WIB"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Wednesday, January 15, 2025 23:00 WIB"))
+        .stderr(predicate::str::contains(
+            "WARNING: 'CST' is ambiguous, using America/Chicago (US Central); \
+             it can also mean Asia/Shanghai (China), America/Havana (Cuba)",
+        ));
+}
+
+#[test]
+fn test_convert_ambiguous_abbreviation_json_warnings() {
+    let value = json_stdout(&["tz", "2025-01-15", "10:00", "IST", "to", "UTC", "--output", "json"]);
+    assert_eq!(value["from"]["zone"], "Asia/Kolkata");
+    assert!(value["warnings"][0].as_str().unwrap().starts_with("'IST' is ambiguous"));
+}
+
+#[test]
+fn test_convert_strict_refuses_ambiguous_abbreviation() {
+    cmd()
+        .args(["tz", "10:00", "BST", "to", "WIB", "--strict"])
+        .assert()
+        .failure()
+        .code(11)
+        .stderr(predicate::str::contains("ERROR: 'BST' matches 2 timezones"))
+        .stderr(predicate::str::contains("Europe/London (British Summer), Asia/Dhaka (Bangladesh)"));
+}
+
+// ===================================
+// Tests for DST gaps and overlaps
+// ===================================
+
+#[test]
+fn test_convert_dst_gap_explains_choice() {
+    cmd()
+        .args(["tz", "2025-03-09", "02:30", "America/New_York", "to", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Sunday, March 09, 2025 06:30 UTC"))
+        .stdout(predicate::str::contains(
+            "NOTE: 02:30 does not exist in America/New_York on 2025-03-09, clocks skip from 02:00 to 03:00 \
+             (EST -> EDT); using 01:30 EST (--dst earliest)",
+        ));
+}
+
+#[test]
+fn test_convert_dst_overlap_latest() {
+    let value = json_stdout(&[
+        "tz", "2025-11-02", "01:30", "America/New_York", "to", "UTC", "--dst", "latest", "--output", "json",
+    ]);
+    assert_eq!(value["to"]["datetime"], "2025-11-02T06:30:00+00:00");
+    assert!(value["dst"].as_str().unwrap().contains("using the second one, 01:30 EST"));
+}
+
+#[test]
+fn test_convert_dst_reject() {
+    cmd()
+        .args(["tz", "2025-11-02", "01:30", "America/New_York", "to", "UTC", "--dst", "reject"])
+        .assert()
+        .failure()
+        .code(12)
+        .stderr(predicate::str::contains("ERROR: 01:30 happens twice in America/New_York"))
+        .stderr(predicate::str::contains("--dst earliest, latest or shift-forward"));
+}
+
+// ===================================
+// Tests for several destinations
+// ===================================
+
+#[test]
+fn test_convert_several_destinations_with_day_markers() {
+    cmd()
+        .args(["tz", "2025-10-09", "23:00", "WIB", "to", "JST,", "PST,", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "TO:   Friday, October 10, 2025 01:00 JST (UTC+09:00)  +2h, +1 day\n      \
+             Thursday, October 09, 2025 09:00 PDT (UTC-07:00) [PST]  -14h\n      \
+             Thursday, October 09, 2025 16:00 UTC (UTC+00:00)  -7h\n",
+        ));
+}
+
+#[test]
+fn test_convert_single_destination_day_marker() {
+    cmd()
+        .args(["tz", "2025-10-09", "23:00", "WIB", "to", "JST"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Friday, October 10, 2025 01:00 JST (UTC+09:00)  +2h, +1 day\n"));
+}
+
+#[test]
+fn test_convert_several_destinations_json() {
+    let value = json_stdout(&["tz", "2025-10-09", "04:00", "UTC+8", "to", "WIB", "PST", "--output", "json"]);
+    assert_eq!(value["to"]["label"], "WIB");
+    assert_eq!(value["destinations"][1]["label"], "PST");
+    assert_eq!(value["destinations"][1]["datetime"], "2025-10-08T13:00:00-07:00");
+    assert_eq!(value["destinations"][1]["day_change"], -1);
+    assert_eq!(value["destinations"][1]["difference_minutes"], -900);
+    assert_eq!(value["difference_minutes"], -60);
+}
+
+#[test]
+fn test_convert_shows_actual_abbreviation_and_offset() {
+    cmd()
+        .args(["tz", "2025-07-01", "12:00", "UTC-8", "to", "America/Los_Angeles"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FROM: Tuesday, July 01, 2025 12:00 UTC-8 (UTC-08:00)\n"))
+        .stdout(predicate::str::contains(
+            "TO:   Tuesday, July 01, 2025 13:00 PDT (UTC-07:00) [America/Los_Angeles]  +1h\n",
+        ));
+
+    cmd()
+        .args(["tz", "2025-10-09", "04:00", "WIB", "to", "Asia/Kolkata"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("IST (UTC+05:30) [Asia/Kolkata]  -1h30m\n"));
+}
+
+// ===================================
+// Tests for the world clock
+// ===================================
+
+#[test]
+fn test_now_default_zones() {
+    cmd()
+        .args(["--now", "2025-10-09T09:30:00+07:00", "now"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("WIB   Thu, Oct 09, 2025 09:30 WIB (UTC+07:00)  working hours"))
+        .stdout(predicate::str::contains("UTC   Thu, Oct 09, 2025 02:30 UTC (UTC+00:00)  night"));
+}
+
+#[test]
+fn test_clock_alias_with_at_json() {
+    let value = json_stdout(&["clock", "PST", "--at", "2025-10-09T02:00:00Z", "--output", "json"]);
+    assert_eq!(value["command"], "now");
+    assert_eq!(value["zones"][0]["datetime"], "2025-10-08T19:00:00-07:00");
+    assert_eq!(value["zones"][0]["period"], "night");
+}
+
+#[test]
+fn test_now_warns_about_ambiguous_abbreviations() {
+    cmd()
+        .args(["--now", "2025-10-09T09:00:00Z", "now", "CST", "WIB"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("WARNING: 'CST' is ambiguous, using America/Chicago (US Central)"));
+
+    let value = json_stdout(&["zone", "IST", "--output", "json"]);
+    assert!(value["warnings"][0].as_str().unwrap().starts_with("'IST' is ambiguous"));
+}
+
+// ===================================
+// Tests for zone information
+// ===================================
+
+#[test]
+fn test_zone_info_text() {
+    cmd()
+        .args(["zone", "New", "York", "--now", "2025-10-09T12:00:00Z"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ZONE:      America/New_York"))
+        .stdout(predicate::str::contains("STANDARD:  EST (UTC-05:00)"))
+        .stdout(predicate::str::contains("DST:       EDT (UTC-04:00), in effect now"))
+        .stdout(predicate::str::contains("PREVIOUS:  Sunday, March 09, 2025 02:00 EST -> 03:00 EDT"))
+        .stdout(predicate::str::contains("NEXT:      Sunday, November 02, 2025 02:00 EDT -> 01:00 EST"));
+}
+
+#[test]
+fn test_zone_info_json() {
+    let value = json_stdout(&["zone", "Europe/Berlin", "--now", "2025-10-09T12:00:00Z", "--output", "json"]);
+    assert_eq!(value["command"], "zone");
+    assert_eq!(value["dst_active"], true);
+    assert_eq!(value["standard"]["offset"], "+01:00");
+    assert_eq!(value["next"]["at"], "2025-10-26T01:00:00+00:00");
+    assert_eq!(value["next"]["after"]["abbreviation"], "CET");
+}
+
+// ===================================
+// Tests for transitions
+// ===================================
+
+#[test]
+fn test_transitions_for_a_year() {
+    cmd()
+        .args(["transitions", "PST", "2025"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "2025-03-09 02:00 -> 03:00 PST->PDT (UTC-08:00 -> UTC-07:00)\n\
+             2025-11-02 02:00 -> 01:00 PDT->PST (UTC-07:00 -> UTC-08:00)\n",
+        ))
+        .stdout(predicate::str::contains("2 transitions"));
+}
+
+#[test]
+fn test_transitions_json() {
+    let value = json_stdout(&["transitions", "Asia/Jakarta", "1960", "1970", "--output", "json"]);
+    assert_eq!(value["zone"], "Asia/Jakarta");
+    assert_eq!(value["transitions"][0]["local_before"], "1964-01-01T00:00:00");
+    assert_eq!(value["transitions"][0]["local_after"], "1963-12-31T23:30:00");
+    assert_eq!(value["transitions"][0]["after"]["abbreviation"], "WIB");
+}
+
+// ===================================
+// Tests for the system timezone
+// ===================================
+
+#[test]
+fn test_convert_to_local_keyword() {
+    cmd()
+        .env("TZ", "Asia/Tokyo")
+        .args(["tz", "2025-10-09", "09:00", "WIB", "to", "local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 11:00 JST (UTC+09:00) [Asia/Tokyo]  +2h"));
+}
+
+#[test]
+fn test_convert_from_here_keyword() {
+    cmd()
+        .env("TZ", ":America/New_York")
+        .args(["tz", "2025-10-09", "09:00", "here", "to", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FROM: Thursday, October 09, 2025 09:00 EDT (UTC-04:00) [America/New_York]"))
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 13:00 UTC (UTC+00:00)  +4h\n"));
+}
+
+#[test]
+fn test_date_outputs_show_local_zone() {
+    cmd()
+        .env("TZ", "Europe/Berlin")
+        .args(["future", "3", "--now", "2025-02-27"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ZONE:         Europe/Berlin (local)"));
+
+    let output = cmd()
+        .env("TZ", "/usr/share/zoneinfo/Asia/Jakarta")
+        .args(["remaining", "month", "--output", "json"])
+        .output()
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["zone"], "Asia/Jakarta");
+}
+
+// ===================================
+// Tests for military zones
+// ===================================
+
+#[test]
+fn test_convert_military_notation_output() {
+    cmd()
+        .args(["tz", "2025-10-09", "0400Z", "to", "WIB,", "UTC+5:30", "--military"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FROM: Thursday, October 09, 2025 0400Z Z (UTC+00:00)"))
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 1100G WIB (UTC+07:00)"))
+        // No letter for a half-hour offset
+        .stdout(predicate::str::contains("Thursday, October 09, 2025 09:30 UTC+5:30 (UTC+05:30)"));
+}
+
+#[test]
+fn test_convert_phonetic_zone_name() {
+    cmd()
+        .args(["tz", "2025-10-09", "1600", "Romeo", "to", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 21:00 UTC"));
+}
+
+#[test]
+fn test_team_command() {
+    let path = config_file("team", "[[team]]\nname = \"Ana\"\nzone = \"WIB\"\n\n[[team]]\nname = \"Kenji\"\nzone = \"JST\"\n");
+
+    cmd()
+        .args(["team", "--at", "2025-10-09 08:30 WIB"])
+        .env("TIMECALC_CONFIG", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ana    Thu, Oct 09, 2025 08:30 WIB (UTC+07:00)  09:00-17:00  off, starts in 30m\n"))
+        .stdout(predicate::str::contains("Kenji  Thu, Oct 09, 2025 10:30 JST (UTC+09:00)  09:00-17:00  working, finishes in 6h30m\n"))
+        .stdout(predicate::str::contains("1 of 2 working now"));
+
+    let output = cmd()
+        .args(["team", "--at", "2025-10-09 08:30 WIB", "--output", "json"])
+        .env("TIMECALC_CONFIG", &path)
+        .output()
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["command"], "team");
+    assert_eq!(value["working"], 1);
+    assert_eq!(value["members"][0]["state"], "off");
+    assert_eq!(value["members"][0]["minutes_until_change"], 30);
+    assert_eq!(value["members"][1]["next_change"], "2025-10-09T17:00:00+09:00");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+// ===================================
+// Tests for the meeting planner
+// ===================================
+
+#[test]
+fn test_meet_command() {
+    cmd()
+        .args(["meet", "WIB", "JST", "--date", "2025-10-20", "--duration", "30m"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("OVERLAP:  WIB  Mon 09:00-15:00 WIB (UTC+07:00)\n          JST  Mon 11:00-17:00 JST (UTC+09:00)\n"))
+        .stdout(predicate::str::contains("SLOT 1:   WIB  Mon 11:30-12:00 WIB (UTC+07:00)\n"))
+        .stdout(predicate::str::contains("5 of 12 slots, best first"));
+
+    cmd()
+        .args(["meet", "WIB", "JST", "PST", "--date", "2025-10-20"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("OVERLAP:  none"))
+        .stdout(predicate::str::contains("No slots"));
+
+    let value = json_stdout(&["meet", "WIB,", "PST", "--date", "2025-10-20", "--hours", "07:00-22:00", "--duration", "2h", "--output", "json"]);
+    assert_eq!(value["command"], "meet");
+    assert_eq!(value["windows"].as_array().unwrap().len(), 2);
+    assert_eq!(value["slots"][0]["margin_minutes"], 90);
+    assert_eq!(value["slots"][0]["local"][1]["start"], "2025-10-19T18:30:00-07:00");
+}
+
+#[test]
+fn test_meet_rejects_bad_hours_and_length() {
+    cmd().args(["meet", "WIB", "JST", "--hours", "9-5"]).assert().failure().code(2);
+    cmd().args(["meet", "WIB", "JST", "--duration", "0m"]).assert().failure().code(2);
+}
+
+#[test]
+fn test_convert_from_now() {
+    cmd()
+        .env("TZ", "Asia/Jakarta")
+        .args(["--now", "2025-10-09T09:30:00+07:00", "tz", "now", "to", "JST"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FROM: Thursday, October 09, 2025 09:30 WIB (UTC+07:00) [Asia/Jakarta]"))
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 11:30 JST (UTC+09:00)  +2h\n"));
+
+    cmd()
+        .args(["--now", "2025-10-09T09:30:00+07:00", "tz", "now+3h", "WIB", "to", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 09, 2025 05:30 UTC (UTC+00:00)  -7h\n"));
+
+    cmd()
+        .args(["--now", "2025-10-09T09:30:00+07:00", "tz", "in", "90", "minutes", "to", "PST"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Wednesday, October 08, 2025 21:00 PDT (UTC-07:00) [PST]"));
+}
+
+#[test]
+fn test_relative_dates() {
+    cmd()
+        .args(["--now", "2025-10-09", "day", "next", "monday"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DATE: October 13, 2025"));
+
+    cmd()
+        .args(["--now", "2025-10-09", "tz", "tomorrow", "09:00", "WIB", "to", "JST"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FROM: Friday, October 10, 2025 09:00 WIB (UTC+07:00)"));
+
+    cmd()
+        .args(["--now", "2025-10-09", "tz", "in", "2", "weeks", "10:00", "WIB", "to", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TO:   Thursday, October 23, 2025 03:00 UTC"));
+
+    cmd()
+        .args(["day", "someday"])
+        .assert()
+        .failure()
+        .code(7)
+        .stderr(predicate::str::contains("next friday"));
+}
@@ -0,0 +1,85 @@
+use clap::Parser;
+use timecalc::cli::{self, Cli, Command};
+use timecalc::render::{OutputFormat, Report};
+use timecalc::*;
+
+fn args(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+fn parse(words: &[&str]) -> Cli {
+    Cli::try_parse_from(cli::normalize_args(args(words))).unwrap()
+}
+
+#[test]
+fn test_normalize_lifts_trailing_globals() {
+    assert_eq!(
+        cli::normalize_args(args(&["timecalc", "tz", "4:00", "UTC", "to", "WIB", "--output", "json"])),
+        args(&["timecalc", "tz", "--output", "json", "4:00", "UTC", "to", "WIB"])
+    );
+    assert_eq!(
+        cli::normalize_args(args(&["timecalc", "--now", "2025-01-01", "tz", "4:00", "--now=2025-02-02"])),
+        args(&["timecalc", "--now", "2025-01-01", "tz", "--now=2025-02-02", "4:00"])
+    );
+}
+
+#[test]
+fn test_normalize_keeps_hyphen_values() {
+    let words = args(&["timecalc", "tz", "4:00", "-03:30", "to", "WIB"]);
+    assert_eq!(cli::normalize_args(words.clone()), words);
+}
+
+#[test]
+fn test_normalize_lowercases_the_command() {
+    assert_eq!(
+        cli::normalize_args(args(&["timecalc", "--output", "json", "TZ", "10:00", "WIB", "to", "JST"])),
+        args(&["timecalc", "--output", "json", "tz", "10:00", "WIB", "to", "JST"])
+    );
+    assert!(matches!(parse(&["timecalc", "Future", "5"]).command, Some(Command::Future { .. })));
+    // Only command names, the expression is left as typed
+    assert_eq!(cli::normalize_args(args(&["timecalc", "Bogus", "TZ"])), args(&["timecalc", "Bogus", "TZ"]));
+}
+
+#[test]
+fn test_parse_negative_days() {
+    match parse(&["timecalc", "future", "-5"]).command {
+        Some(Command::Future { days }) => assert_eq!(days, args(&["-5"])),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(matches!(parse(&["timecalc", "past", "-7d", "--output", "json"]).command, Some(Command::Past { .. })));
+}
+
+#[test]
+fn test_parse_free_form_expression() {
+    let cli = parse(&["timecalc", "tz", "October", "9,", "2025", "at", "04:00AM", "UTC+8", "to", "WIB"]);
+    match cli.command {
+        Some(Command::Convert { expression }) => assert_eq!(expression.len(), 8),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_aliases() {
+    assert!(matches!(parse(&["timecalc", "date", "5"]).command, Some(Command::Future { .. })));
+    assert!(matches!(parse(&["timecalc", "left", "month"]).command, Some(Command::Remaining { .. })));
+    assert!(matches!(parse(&["timecalc", "tz", "4:00", "UTC", "to", "WIB"]).command, Some(Command::Convert { .. })));
+}
+
+#[test]
+fn test_parse_global_flags() {
+    let cli = parse(&["timecalc", "remaining", "MONTH", "--output", "json", "--now", "2025-02-27"]);
+    assert_eq!(cli.output, Some(OutputFormat::Json));
+
+    let clock = cli.clock().unwrap();
+    let report = cli::execute(cli.command.as_ref().unwrap(), clock.as_ref()).unwrap();
+    match report {
+        Report::Remaining(rem) => assert_eq!(rem.remaining, 1),
+        other => panic!("unexpected report: {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_now() {
+    let cli = parse(&["timecalc", "--now", "whenever", "day", "2025-12-25"]);
+    assert!(matches!(cli.clock(), Err(TimecalcError::InvalidDateTime(_))));
+}
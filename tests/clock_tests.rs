@@ -0,0 +1,38 @@
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use timecalc::*;
+
+#[test]
+fn test_fixed_clock_parse_date_only() {
+    let clock = FixedClock::parse("2025-02-27").unwrap();
+    assert_eq!(clock.today(), NaiveDate::from_ymd_opt(2025, 2, 27).unwrap());
+    assert_eq!(clock.now().time(), NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+}
+
+#[test]
+fn test_fixed_clock_parse_date_time() {
+    let clock = FixedClock::parse("2025-02-27 09:30").unwrap();
+    assert_eq!(clock.now().hour(), 9);
+    assert_eq!(clock.now().minute(), 30);
+
+    let clock = FixedClock::parse("2025-02-27T09:30:15").unwrap();
+    assert_eq!(clock.now().second(), 15);
+}
+
+#[test]
+fn test_fixed_clock_parse_rfc3339() {
+    let clock = FixedClock::parse("2025-02-27T09:30:00+07:00").unwrap();
+    assert_eq!(clock.now().timestamp(), 1740623400);
+}
+
+#[test]
+fn test_fixed_clock_parse_invalid() {
+    assert!(FixedClock::parse("yesterday-ish").is_none());
+    assert!(FixedClock::parse("2025-02-30").is_none());
+}
+
+#[test]
+fn test_system_clock_is_now() {
+    let before = chrono::Local::now();
+    let now = SystemClock.now();
+    assert!(now >= before);
+}
@@ -0,0 +1,53 @@
+use std::ffi::OsStr;
+
+use timecalc::completions::{self, CompletionShell};
+
+fn complete(current: &str) -> Vec<String> {
+    completions::complete_expression(OsStr::new(current))
+        .iter()
+        .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_zone_names_include_aliases() {
+    let names = completions::zone_names();
+    for alias in ["WIB", "WITA", "WIT", "SGT", "MYT", "UTC", "PST", "EST", "JST"] {
+        assert!(names.iter().any(|name| name == alias), "missing {}", alias);
+    }
+}
+
+#[test]
+fn test_zone_names_are_accepted() {
+    for name in completions::zone_names() {
+        assert!(timecalc::parse_timezone(&name).is_some(), "{} is not accepted", name);
+    }
+}
+
+#[test]
+fn test_complete_expression_prefix() {
+    // Aliases come before the city names
+    let wi = complete("wi");
+    assert_eq!(&wi[..3], ["WIB", "WITA", "WIT"]);
+    assert!(wi.contains(&"Winnipeg".to_string()));
+    // Every IANA name is accepted now, so "T" also matches Turkey
+    assert_eq!(complete("T").first().map(String::as_str), Some("to"));
+    assert_eq!(complete("europe/berl"), vec!["Europe/Berlin"]);
+    assert!(complete("").len() > 10);
+    assert!(complete("xyz").is_empty());
+}
+
+#[test]
+fn test_registration_scripts() {
+    for (shell, marker) in [
+        (CompletionShell::Bash, "complete -o nospace"),
+        (CompletionShell::Zsh, "#compdef timecalc"),
+        (CompletionShell::Fish, "complete --keep-order"),
+    ] {
+        let mut script = Vec::new();
+        completions::write_registration(shell, &mut script).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains(marker), "{:?} script: {}", shell, script);
+        assert!(script.contains("COMPLETE="));
+    }
+}
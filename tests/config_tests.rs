@@ -0,0 +1,242 @@
+use chrono::Weekday;
+use timecalc::config::{self, TimeFormat};
+use timecalc::render::{self, OutputFormat, Report};
+use timecalc::*;
+
+fn clock() -> FixedClock {
+    FixedClock::parse("2025-02-27 09:30").unwrap()
+}
+
+fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn test_config_defaults() {
+    let config = Config::parse("").unwrap();
+
+    assert_eq!(config, Config::default());
+    assert_eq!(config.output, OutputFormat::Text);
+    assert_eq!(config.time_format, TimeFormat::H24);
+    assert_eq!(config.week_start, Weekday::Mon);
+    assert!(config.default_zone.is_none());
+}
+
+#[test]
+fn test_config_parse_all_settings() {
+    let config = Config::parse(
+        "default_zone = \"WIB\"\noutput = \"json\"\ntime_format = \"12h\"\nweek_start = \"Sunday\"\n\n\
+         [aliases]\noffice = \"Asia/Jakarta\"\nhome = \"wita\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(config.default_zone.as_deref(), Some("WIB"));
+    assert_eq!(config.output, OutputFormat::Json);
+    assert_eq!(config.time_format, TimeFormat::H12);
+    assert_eq!(config.week_start, Weekday::Sun);
+    assert_eq!(config.alias("OFFICE"), Some(chrono_tz::Asia::Jakarta.into()));
+    assert_eq!(config.alias("home"), Some(chrono_tz::Asia::Makassar.into()));
+}
+
+#[test]
+fn test_config_rejects_bad_values() {
+    assert!(Config::parse("output = \"xml\"").unwrap_err().contains("output"));
+    assert!(Config::parse("time_format = \"13h\"").unwrap_err().contains("time_format"));
+    assert!(Config::parse("week_start = \"someday\"").unwrap_err().contains("week_start"));
+    assert!(Config::parse("default_zone = \"MARS\"").unwrap_err().contains("MARS"));
+    assert!(Config::parse("[aliases]\nbase = \"Mars/Olympus\"").unwrap_err().contains("base"));
+    assert!(Config::parse("colour = \"red\"").unwrap_err().contains("colour"));
+    assert!(Config::parse("dst = \"sometimes\"").unwrap_err().contains("dst"));
+    assert!(Config::parse("clock_zones = [\"WIB\", \"Atlantis\"]").unwrap_err().contains("Atlantis"));
+}
+
+#[test]
+fn test_config_missing_file_uses_defaults() {
+    let config = Config::load_from(std::path::Path::new("/nonexistent/timecalc/config.toml")).unwrap();
+
+    assert!(!config.loaded);
+    assert_eq!(config.output, OutputFormat::Text);
+}
+
+#[test]
+fn test_user_alias_consulted_before_builtin() {
+    // Installed per thread, so this does not leak into other tests
+    config::install(Config::parse("[aliases]\noffice = \"Asia/Tokyo\"\nwib = \"UTC\"").unwrap());
+
+    assert_eq!(parse_timezone("office"), Some(chrono_tz::Asia::Tokyo.into()));
+    assert_eq!(parse_timezone("WIB"), Some(chrono_tz::Etc::UTC.into()));
+    assert_eq!(parse_timezone("JST"), Some(chrono_tz::Asia::Tokyo.into()));
+}
+
+#[test]
+fn test_default_zone_without_to() {
+    config::install(Config::parse("default_zone = \"WIB\"").unwrap());
+
+    let conv = handle_timezone_convert(&args("2025-10-09 10:00 PST"), &clock()).unwrap();
+    assert_eq!(conv.to_label, "WIB");
+    assert_eq!(conv.to.format("%Y-%m-%d %H:%M").to_string(), "2025-10-10 00:00");
+
+    // An explicit destination still wins
+    let conv = handle_timezone_convert(&args("10:00 PST to JST"), &clock()).unwrap();
+    assert_eq!(conv.to_label, "JST");
+}
+
+#[test]
+fn test_twelve_hour_rendering() {
+    config::install(Config::parse("time_format = \"12h\"").unwrap());
+
+    let conv = handle_timezone_convert(&args("2025-10-09 16:00 UTC to WIB"), &clock()).unwrap();
+    assert!(render::text(&Report::Conversion(conv)).contains("TO:   Thursday, October 09, 2025 11:00 PM WIB"));
+}
+
+#[test]
+fn test_prefer_regions_picks_abbreviation_meaning() {
+    config::install(Config::parse("prefer_regions = [\"ie\", \"CN\"]").unwrap());
+
+    assert_eq!(parse_timezone("IST"), Some(chrono_tz::Europe::Dublin.into()));
+    assert_eq!(parse_timezone("CST"), Some(chrono_tz::Asia::Shanghai.into()));
+    // Settled by the preference, nothing to warn about
+    assert!(abbreviation_warning("IST").is_none());
+    // BST is not covered by the preference
+    assert!(abbreviation_warning("BST").unwrap().contains("using Europe/London"));
+}
+
+#[test]
+fn test_strict_refuses_ambiguous_abbreviations() {
+    config::install(Config::parse("strict = true\nprefer_regions = [\"IN\"]").unwrap());
+
+    match resolve_timezone("CST") {
+        Err(TimecalcError::AmbiguousTimezone { name, candidates }) => {
+            assert_eq!(name, "CST");
+            assert_eq!(candidates[0], "America/Chicago (US Central)");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    // Settled by prefer_regions, so strict mode lets it through
+    assert_eq!(resolve_timezone("IST"), Ok(chrono_tz::Asia::Kolkata.into()));
+    assert!(resolve_timezone("PST").is_ok());
+}
+
+#[test]
+fn test_conversion_carries_warnings() {
+    let conv = handle_timezone_convert(&args("2025-01-15 10:00 CST to WIB"), &clock()).unwrap();
+
+    assert_eq!(conv.warnings.len(), 1);
+    assert!(conv.warnings[0].starts_with("'CST' is ambiguous, using America/Chicago (US Central)"));
+    assert_eq!(conv.to.format("%H:%M").to_string(), "23:00");
+}
+
+#[test]
+fn test_other_commands_carry_warnings() {
+    let world = handle_now(&args("CST IST CST"), None, &clock()).unwrap();
+    assert_eq!(world.warnings.len(), 2);
+    assert!(world.warnings[1].starts_with("'IST' is ambiguous, using Asia/Kolkata (India)"));
+    assert_eq!(render::warnings(&Report::Clock(world)).len(), 2);
+
+    let plan = handle_meet(&args("CST JST"), None, WorkingHours::default(), chrono::Duration::hours(1), &clock()).unwrap();
+    assert_eq!(plan.warnings.len(), 1);
+    assert_eq!(handle_zone_info(&args("IST"), &clock()).unwrap().warnings.len(), 1);
+    assert_eq!(handle_transitions(&args("IST 2025"), &clock()).unwrap().warnings.len(), 1);
+    assert!(handle_zone_info(&args("WIB"), &clock()).unwrap().warnings.is_empty());
+
+    config::install(Config::parse("[[team]]\nname = \"Priya\"\nzone = \"IST\"").unwrap());
+    let team = handle_team(Some("2025-10-09 09:00 CST"), &clock()).unwrap();
+    assert_eq!(team.warnings.len(), 2);
+    assert!(team.warnings[0].starts_with("'CST'"));
+
+    // Once the region is preferred there is nothing to warn about
+    config::install(Config::parse("prefer_regions = [\"IN\"]\n[[team]]\nname = \"Priya\"\nzone = \"IST\"").unwrap());
+    assert!(handle_team(None, &clock()).unwrap().warnings.is_empty());
+}
+
+#[test]
+fn test_dst_policy_from_config() {
+    assert_eq!(Config::parse("").unwrap().dst, DstPolicy::Earliest);
+    assert_eq!(Config::parse("dst = \"Shift-Forward\"").unwrap().dst, DstPolicy::ShiftForward);
+
+    config::install(Config::parse("dst = \"reject\"").unwrap());
+    let err = handle_timezone_convert(&args("2025-03-09 02:30 PST to UTC"), &clock()).unwrap_err();
+    assert!(matches!(err, TimecalcError::DstTransition(_)));
+    assert!(handle_timezone_convert(&args("2025-03-10 02:30 PST to UTC"), &clock()).is_ok());
+}
+
+#[test]
+fn test_clock_zones_from_config() {
+    assert_eq!(Config::default().clock_zones, ["WIB", "WITA", "WIT", "UTC"]);
+
+    config::install(Config::parse("clock_zones = [\"Tokyo\", \"office\"]\n[aliases]\noffice = \"Europe/Berlin\"").unwrap());
+    let world = handle_now(&[], None, &clock()).unwrap();
+    let labels: Vec<_> = world.times.iter().map(|dest| dest.label.as_str()).collect();
+    assert_eq!(labels, ["Tokyo", "OFFICE"]);
+}
+
+const TEAM: &str = "\
+[[team]]
+name = \"Ana\"
+zone = \"WIB\"
+
+[[team]]
+name = \"Budi\"
+zone = \"office\"
+hours = \"08:00-16:00\"
+weekend = [\"friday\", \"saturday\"]
+
+[[team]]
+name = \"Sam\"
+zone = \"PST\"
+hours = \"22:00-06:00\"
+
+[aliases]
+office = \"Asia/Makassar\"
+";
+
+#[test]
+fn test_team_from_config() {
+    let config = Config::parse(TEAM).unwrap();
+    assert_eq!(config.team.len(), 3);
+    assert_eq!(config.team[0].hours, WorkingHours::default());
+    assert_eq!(config.team[0].weekend, [Weekday::Sat, Weekday::Sun]);
+    assert_eq!(config.team[1].hours.to_string(), "08:00-16:00");
+    assert_eq!(config.team[1].weekend, [Weekday::Fri, Weekday::Sat]);
+
+    assert!(Config::parse("[[team]]\nname = \"Ana\"\nzone = \"Atlantis\"").unwrap_err().contains("Atlantis"));
+    assert!(Config::parse("[[team]]\nname = \"Ana\"\nzone = \"WIB\"\nhours = \"9 to 5\"").unwrap_err().contains("hours"));
+    assert!(Config::parse("[[team]]\nname = \"Ana\"\nzone = \"WIB\"\nweekend = [\"caturday\"]").unwrap_err().contains("caturday"));
+    assert!(Config::parse("[[team]]\nname = \"Ana\"").unwrap_err().contains("zone"));
+}
+
+#[test]
+fn test_team_status() {
+    config::install(Config::parse(TEAM).unwrap());
+    // Friday 09:30 in Jakarta, 10:30 in Makassar, Thursday 19:30 in Los Angeles
+    let team = handle_team(Some("2025-10-10 09:30 WIB"), &clock()).unwrap();
+    let [ana, budi, sam] = &team.members[..] else { panic!("expected three members") };
+
+    assert!(ana.working && !ana.day_off);
+    assert_eq!(ana.change.unwrap().to_rfc3339(), "2025-10-10T17:00:00+07:00");
+
+    assert_eq!(budi.label, "OFFICE");
+    assert!(!budi.working && budi.day_off);
+    assert_eq!(budi.change.unwrap().to_rfc3339(), "2025-10-12T08:00:00+08:00");
+
+    assert!(!sam.working);
+    assert_eq!(sam.change.unwrap().to_rfc3339(), "2025-10-09T22:00:00-07:00");
+
+    // A shift running past midnight is still on the next morning
+    let team = handle_team(Some("2025-10-10 03:00 PST"), &clock()).unwrap();
+    assert!(team.members[2].working);
+    assert_eq!(team.members[2].change.unwrap().to_rfc3339(), "2025-10-10T06:00:00-07:00");
+
+    let out = render::render(&Report::Team(team), OutputFormat::Text);
+    assert!(out.contains("Sam   Fri, Oct 10, 2025 03:00 PDT (UTC-07:00) [PST]"));
+    assert!(out.contains("22:00-06:00  working, finishes in 3h\n"));
+    assert!(out.contains("weekend, starts in 1d 14h\n"));
+    assert!(out.contains("1 of 3 working now"));
+}
+
+#[test]
+fn test_team_requires_roster() {
+    config::install(Config::default());
+    let err = handle_team(None, &clock()).unwrap_err();
+    assert!(matches!(err, TimecalcError::MissingArgument { .. }));
+}
@@ -1,82 +1,87 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use timecalc::*;
 
+fn clock() -> FixedClock {
+    FixedClock::parse("2025-02-27 09:30").unwrap()
+}
+
 // Handler function tests to increase coverage
 #[test]
 fn test_handle_future_date_valid() {
     let args = vec!["10".to_string()];
-    handle_future_date(&args);
+    assert!(handle_future_date(&args, &clock()).is_ok());
 }
 
 #[test]
 fn test_handle_future_date_empty() {
     let args: Vec<String> = vec![];
-    handle_future_date(&args);
+    assert!(handle_future_date(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_future_date_invalid() {
     let args = vec!["invalid".to_string()];
-    handle_future_date(&args);
+    assert!(handle_future_date(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_past_date_valid() {
     let args = vec!["7".to_string()];
-    handle_past_date(&args);
+    assert!(handle_past_date(&args, &clock()).is_ok());
 }
 
 #[test]
 fn test_handle_past_date_empty() {
     let args: Vec<String> = vec![];
-    handle_past_date(&args);
+    assert!(handle_past_date(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_past_date_invalid() {
     let args = vec!["invalid".to_string()];
-    handle_past_date(&args);
+    assert!(handle_past_date(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_day_of_week_valid() {
     let args = vec!["2025-12-25".to_string()];
-    handle_day_of_week(&args);
+    assert!(handle_day_of_week(&args, &clock()).is_ok());
 }
 
 #[test]
 fn test_handle_day_of_week_empty() {
     let args: Vec<String> = vec![];
-    handle_day_of_week(&args);
+    assert!(handle_day_of_week(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_day_of_week_invalid() {
     let args = vec!["invalid-date".to_string()];
-    handle_day_of_week(&args);
+    assert!(handle_day_of_week(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_remaining_month() {
     let args = vec!["month".to_string()];
-    handle_remaining(&args);
+    assert!(handle_remaining(&args, &clock()).is_ok());
 }
 
 #[test]
 fn test_handle_remaining_year() {
     let args = vec!["year".to_string()];
-    handle_remaining(&args);
+    assert!(handle_remaining(&args, &clock()).is_ok());
 }
 
 #[test]
 fn test_handle_remaining_empty() {
     let args: Vec<String> = vec![];
-    handle_remaining(&args);
+    assert!(handle_remaining(&args, &clock()).is_err());
 }
 
 #[test]
 fn test_handle_remaining_invalid() {
     let args = vec!["invalid".to_string()];
-    handle_remaining(&args);
+    assert!(handle_remaining(&args, &clock()).is_err());
 }
 
 #[test]
@@ -87,13 +92,13 @@ fn test_handle_timezone_convert_valid() {
         "to".to_string(),
         "PST".to_string(),
     ];
-    handle_timezone_convert(&args);
+    assert!(handle_timezone_convert(&args, &clock()).is_ok());
 }
 
 #[test]
 fn test_handle_timezone_convert_insufficient_args() {
     let args = vec!["04:00AM".to_string()];
-    handle_timezone_convert(&args);
+    assert!(handle_timezone_convert(&args, &clock()).is_err());
 }
 
 #[test]
@@ -104,7 +109,7 @@ fn test_handle_timezone_convert_no_to_keyword() {
         "from".to_string(),
         "PST".to_string(),
     ];
-    handle_timezone_convert(&args);
+    assert!(handle_timezone_convert(&args, &clock()).is_err());
 }
 
 #[test]
@@ -115,7 +120,7 @@ fn test_handle_timezone_convert_invalid_datetime() {
         "to".to_string(),
         "PST".to_string(),
     ];
-    handle_timezone_convert(&args);
+    assert!(handle_timezone_convert(&args, &clock()).is_err());
 }
 
 #[test]
@@ -126,7 +131,7 @@ fn test_handle_timezone_convert_invalid_timezone() {
         "to".to_string(),
         "PST".to_string(),
     ];
-    handle_timezone_convert(&args);
+    assert!(handle_timezone_convert(&args, &clock()).is_err());
 }
 
 #[test]
@@ -140,7 +145,71 @@ fn test_handle_timezone_convert_with_date() {
         "to".to_string(),
         "WIB".to_string(),
     ];
-    handle_timezone_convert(&args);
+    assert!(handle_timezone_convert(&args, &clock()).is_ok());
+}
+
+#[test]
+fn test_handle_future_date_result() {
+    let args = vec!["10".to_string()];
+    let calc = handle_future_date(&args, &clock()).unwrap();
+    assert_eq!(calc.days, 10);
+    assert_eq!(calc.direction, Direction::Future);
+    assert_eq!((calc.date - calc.today).num_days(), 10);
+    assert_eq!(calc.date.date_naive(), NaiveDate::from_ymd_opt(2025, 3, 9).unwrap());
+}
+
+#[test]
+fn test_handle_past_date_result() {
+    let args = vec!["7".to_string()];
+    let calc = handle_past_date(&args, &clock()).unwrap();
+    assert_eq!(calc.days, 7);
+    assert_eq!(calc.direction, Direction::Past);
+    assert_eq!((calc.today - calc.date).num_days(), 7);
+    assert_eq!(calc.date.date_naive(), NaiveDate::from_ymd_opt(2025, 2, 20).unwrap());
+}
+
+#[test]
+fn test_handle_day_of_week_result() {
+    let args = vec!["2025-12-25".to_string()];
+    let info = handle_day_of_week(&args, &clock()).unwrap();
+    assert_eq!(info.weekday, Weekday::Thu);
+}
+
+#[test]
+fn test_handle_remaining_result() {
+    let args = vec!["year".to_string()];
+    let rem = handle_remaining(&args, &clock()).unwrap();
+    assert_eq!(rem.period, Period::Year);
+    assert_eq!(rem.end.month(), 12);
+    assert_eq!(rem.end.day(), 31);
+    assert_eq!(rem.remaining, 307);
+    assert_eq!(rem.passed, 58);
+}
+
+#[test]
+fn test_handle_remaining_month_result() {
+    let args = vec!["month".to_string()];
+    let rem = handle_remaining(&args, &clock()).unwrap();
+    assert_eq!(rem.period, Period::Month);
+    assert_eq!(rem.end, NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
+    assert_eq!(rem.remaining, 1);
+    assert_eq!(rem.passed, 27);
+}
+
+#[test]
+fn test_handle_timezone_convert_result() {
+    let args = vec![
+        "2025-10-09".to_string(),
+        "04:00".to_string(),
+        "UTC+8".to_string(),
+        "to".to_string(),
+        "WIB".to_string(),
+    ];
+    let conv = handle_timezone_convert(&args, &clock()).unwrap();
+    assert_eq!(conv.from.naive_local().to_string(), "2025-10-09 04:00:00");
+    assert_eq!(conv.to.naive_local().to_string(), "2025-10-09 03:00:00");
+    assert_eq!(conv.from_label, "UTC+8");
+    assert_eq!(conv.to_label, "WIB");
 }
 
 #[test]
@@ -148,4 +217,328 @@ fn test_print_help_executes() {
     // Just call print_help to increase coverage
     // This test ensures the function executes without panicking
     print_help();
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_handler_errors() {
+    let args = vec!["invalid".to_string()];
+    assert_eq!(handle_future_date(&args, &clock()), Err(TimecalcError::InvalidDays("invalid".to_string())));
+    assert_eq!(handle_remaining(&args, &clock()), Err(TimecalcError::InvalidPeriod("invalid".to_string())));
+    assert_eq!(handle_day_of_week(&args, &clock()), Err(TimecalcError::InvalidDate("invalid".to_string())));
+
+    let args: Vec<String> = ["04:00AM", "UTC", "from", "PST"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(handle_timezone_convert(&args, &clock()), Err(TimecalcError::MissingTo));
+
+    let args: Vec<String> = ["04:00AM", "MARS", "to", "PST"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(
+        handle_timezone_convert(&args, &clock()),
+        Err(TimecalcError::UnsupportedTimezone("MARS".to_string()))
+    );
+
+    let args: Vec<String> = ["to", "PST", "and", "more"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(handle_timezone_convert(&args, &clock()), Err(TimecalcError::InvalidDateTime(String::new())));
+}
+
+#[test]
+fn test_handle_days_out_of_range() {
+    // Beyond the dates chrono can represent, instead of a panic
+    let args = vec!["100000000000".to_string()];
+    assert_eq!(handle_future_date(&args, &clock()), Err(TimecalcError::InvalidDays("100000000000".to_string())));
+    assert_eq!(handle_past_date(&args, &clock()), Err(TimecalcError::InvalidDays("100000000000".to_string())));
+
+    let args = vec!["-100000000000".to_string()];
+    assert_eq!(handle_future_date(&args, &clock()), Err(TimecalcError::InvalidDays("-100000000000".to_string())));
+}
+
+#[test]
+fn test_error_exit_codes_are_distinct() {
+    let errors = [
+        TimecalcError::MissingArgument { message: "foo", example: "foo" },
+        TimecalcError::InvalidDays("foo".to_string()),
+        TimecalcError::InvalidDateTime("foo".to_string()),
+        TimecalcError::UnsupportedTimezone("foo".to_string()),
+        TimecalcError::MissingTo,
+        TimecalcError::InvalidDate("foo".to_string()),
+    ];
+
+    let mut codes: Vec<i32> = errors.iter().map(|e| e.exit_code()).collect();
+    assert!(codes.iter().all(|&code| code > 1));
+    codes.dedup();
+    assert_eq!(codes.len(), errors.len());
+}
+
+#[test]
+fn test_handle_timezone_convert_iana_names_with_spaces() {
+    let args: Vec<String> = "2025-10-09 10:00 America/Sao Paulo to Europe/Berlin"
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let conv = handle_timezone_convert(&args, &clock()).unwrap();
+
+    assert_eq!(conv.from_label, "America/Sao_Paulo");
+    assert_eq!(conv.to_label, "Europe/Berlin");
+    assert_eq!(conv.to.format("%Y-%m-%d %H:%M").to_string(), "2025-10-09 15:00");
+}
+
+#[test]
+fn test_handle_zones_search() {
+    let list = handle_zones(Some("jakarta"), &clock());
+    assert_eq!(list.zones.len(), 1);
+    assert_eq!(list.zones[0].timezone().name(), "Asia/Jakarta");
+
+    let list = handle_zones(Some("new york"), &clock());
+    assert_eq!(list.zones[0].timezone().name(), "America/New_York");
+
+    assert!(handle_zones(Some("mars"), &clock()).zones.is_empty());
+    assert!(handle_zones(None, &clock()).zones.len() > 500);
+}
+
+#[test]
+fn test_handle_timezone_convert_city_names() {
+    let args: Vec<String> = "2025-10-09 10:00 New York to Bandung".split_whitespace().map(String::from).collect();
+    let conv = handle_timezone_convert(&args, &clock()).unwrap();
+
+    assert_eq!(conv.from_label, "New York");
+    assert_eq!(conv.to_label, "Bandung");
+    assert_eq!(conv.to.format("%Y-%m-%d %H:%M").to_string(), "2025-10-09 21:00");
+}
+
+#[test]
+fn test_handle_where() {
+    let args = vec!["Makassar".to_string()];
+    let location = handle_where(&args, &clock()).unwrap();
+    assert_eq!(location.name, "Makassar");
+    assert_eq!(location.times.len(), 1);
+    assert_eq!(location.times[0].format("%Z").to_string(), "WITA");
+
+    let args = vec!["Indonesia".to_string()];
+    assert_eq!(handle_where(&args, &clock()).unwrap().times.len(), 3);
+
+    let args = vec!["Atlantis".to_string()];
+    assert_eq!(handle_where(&args, &clock()), Err(TimecalcError::UnknownPlace("Atlantis".to_string())));
+}
+
+#[test]
+fn test_handle_timezone_convert_several_destinations() {
+    let args: Vec<String> = "2025-10-09 04:00 UTC+8 to WIB, JST, PST".split_whitespace().map(String::from).collect();
+    let conv = handle_timezone_convert(&args, &clock()).unwrap();
+
+    assert_eq!(conv.to_label, "WIB");
+    let also: Vec<_> = conv
+        .also
+        .iter()
+        .map(|dest| format!("{} {}", dest.datetime.format("%Y-%m-%d %H:%M"), dest.label))
+        .collect();
+    assert_eq!(also, ["2025-10-09 05:00 JST", "2025-10-08 13:00 PST"]);
+}
+
+#[test]
+fn test_handle_timezone_convert_destinations_without_commas() {
+    let args: Vec<String> = "2025-10-09 10:00 WIB to JST New York america/sao paulo"
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let conv = handle_timezone_convert(&args, &clock()).unwrap();
+
+    let labels: Vec<_> = conv.also.iter().map(|dest| dest.label.as_str()).collect();
+    assert_eq!(conv.to_label, "JST");
+    assert_eq!(labels, ["New York", "America/Sao_Paulo"]);
+
+    let args: Vec<String> = "10:00 WIB to JST Mars".split_whitespace().map(String::from).collect();
+    assert_eq!(
+        handle_timezone_convert(&args, &clock()),
+        Err(TimecalcError::UnsupportedTimezone("Mars".to_string()))
+    );
+}
+
+#[test]
+fn test_handle_now_default_zones() {
+    let world = handle_now(&[], None, &FixedClock::parse("2025-10-09T09:30:00+07:00").unwrap()).unwrap();
+
+    let times: Vec<_> = world
+        .times
+        .iter()
+        .map(|dest| format!("{} {}", dest.label, dest.datetime.format("%H:%M")))
+        .collect();
+    assert_eq!(times, ["WIB 09:30", "WITA 10:30", "WIT 11:30", "UTC 02:30"]);
+    assert_eq!(DayPeriod::of(&world.times[0].datetime), DayPeriod::Working);
+    assert_eq!(DayPeriod::of(&world.times[3].datetime), DayPeriod::Night);
+}
+
+#[test]
+fn test_handle_now_at_instant_in_zone() {
+    let zones: Vec<String> = "JST, New York".split_whitespace().map(String::from).collect();
+    let world = handle_now(&zones, Some("2025-10-11 09:00 WIB"), &clock()).unwrap();
+
+    assert_eq!(world.times[0].datetime.to_rfc3339(), "2025-10-11T11:00:00+09:00");
+    assert_eq!(world.times[1].label, "New York");
+    assert_eq!(world.times[1].datetime.to_rfc3339(), "2025-10-10T22:00:00-04:00");
+    // Saturday morning is daytime, not working hours
+    assert_eq!(DayPeriod::of(&world.times[0].datetime), DayPeriod::Daytime);
+
+    assert!(matches!(handle_now(&[], Some("soon"), &clock()), Err(TimecalcError::InvalidDateTime(_))));
+}
+
+#[test]
+fn test_handle_zone_info_dst_zone() {
+    let info = handle_zone_info(&["PST".to_string()], &clock()).unwrap();
+
+    assert_eq!(info.now.timezone().name(), "America/Los_Angeles");
+    assert_eq!(info.standard.to_string(), "PST");
+    assert_eq!(info.dst.unwrap().to_string(), "PDT");
+    assert!(!info.now.offset().is_dst());
+    assert_eq!(info.previous.unwrap().at.to_rfc3339(), "2024-11-03T09:00:00+00:00");
+    assert_eq!(info.next.unwrap().at.to_rfc3339(), "2025-03-09T10:00:00+00:00");
+}
+
+#[test]
+fn test_handle_zone_info_without_dst() {
+    let info = handle_zone_info(&["Bandung".to_string()], &clock()).unwrap();
+
+    assert_eq!(info.now.timezone().name(), "Asia/Jakarta");
+    assert_eq!(info.standard.to_string(), "WIB");
+    assert!(info.dst.is_none());
+    assert!(info.next.is_none());
+
+    assert!(matches!(handle_zone_info(&[], &clock()), Err(TimecalcError::MissingArgument { .. })));
+}
+
+#[test]
+fn test_handle_zone_info_canonical_name() {
+    // Aliases and backward-compatible links show the zone they stand for
+    let name = |input: &str| handle_zone_info(&[input.to_string()], &clock()).unwrap().now.timezone().name();
+    assert_eq!(name("JST"), "Asia/Tokyo");
+    assert_eq!(name("Japan"), "Asia/Tokyo");
+    assert_eq!(name("EST"), "America/New_York");
+    assert_eq!(name("US/Pacific"), "America/Los_Angeles");
+    assert_eq!(name("Europe/Berlin"), "Europe/Berlin");
+    assert_eq!(name("UTC+5:30"), "UTC+05:30");
+}
+
+fn words(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn test_handle_transitions_ranges() {
+    // This year by default
+    let list = handle_transitions(&words("PST"), &clock()).unwrap();
+    assert_eq!((list.from.to_string(), list.to.to_string()), ("2025-01-01".to_string(), "2025-12-31".to_string()));
+    assert_eq!(list.transitions.len(), 2);
+
+    let list = handle_transitions(&words("New York 2020 2021"), &clock()).unwrap();
+    assert_eq!(list.zone.name(), "America/New_York");
+    assert_eq!(list.transitions.len(), 4);
+
+    let list = handle_transitions(&words("Europe/Berlin 2025-03-01 2025-06-30"), &clock()).unwrap();
+    assert_eq!(list.transitions.len(), 1);
+    assert_eq!(list.transitions[0].local_before().to_string(), "2025-03-30 02:00:00");
+    assert_eq!(list.transitions[0].local_after().to_string(), "2025-03-30 03:00:00");
+}
+
+#[test]
+fn test_handle_transitions_errors() {
+    assert!(matches!(handle_transitions(&words("PST 2025-13-01"), &clock()), Err(TimecalcError::InvalidYearOrDate(_))));
+    // The token that failed is reported, wherever it is in the range
+    assert_eq!(
+        handle_transitions(&words("PST 2025-13-01 2025-12-01"), &clock()),
+        Err(TimecalcError::InvalidYearOrDate("2025-13-01".to_string()))
+    );
+    assert_eq!(
+        handle_transitions(&words("PST 2025-01-01 2025-12-32"), &clock()),
+        Err(TimecalcError::InvalidYearOrDate("2025-12-32".to_string()))
+    );
+    assert_eq!(
+        handle_transitions(&words("PST 2026 2025"), &clock()),
+        Err(TimecalcError::ReversedRange { from: "2026-01-01".to_string(), to: "2025-12-31".to_string() })
+    );
+    assert!(matches!(handle_transitions(&words("Mars 2025"), &clock()), Err(TimecalcError::UnsupportedTimezone(_))));
+}
+
+#[test]
+fn test_handle_timezone_convert_military_times() {
+    let cases = [
+        ("2025-10-09 0400Z to WIB", "2025-10-09 11:00"),
+        ("2025-10-09T04:00Z to WIB", "2025-10-09 11:00"),
+        ("2025-10-09 1600 Romeo to WIB", "2025-10-10 04:00"),
+        ("2025-10-09 16:00R to WIB", "2025-10-10 04:00"),
+        ("2025-10-09 0400 zulu to WIB", "2025-10-09 11:00"),
+    ];
+    for (input, expected) in cases {
+        let conv = handle_timezone_convert(&words(input), &clock()).unwrap();
+        assert_eq!(conv.to.format("%Y-%m-%d %H:%M").to_string(), expected, "{}", input);
+    }
+}
+
+#[test]
+fn test_handle_meet_overlap_and_ranking() {
+    let plan = handle_meet(&words("WIB JST"), Some("2025-10-20"), WorkingHours::default(), Duration::hours(1), &clock()).unwrap();
+
+    // 09:00-15:00 WIB is 11:00-17:00 JST
+    assert_eq!(plan.windows.len(), 1);
+    assert_eq!(plan.windows[0].0.to_rfc3339(), "2025-10-20T02:00:00+00:00");
+    assert_eq!(plan.windows[0].1.to_rfc3339(), "2025-10-20T08:00:00+00:00");
+    assert_eq!(plan.slots.len(), 11);
+
+    // The slot furthest from anyone's start or end of day comes first
+    assert_eq!(plan.slots[0].start.to_rfc3339(), "2025-10-20T04:30:00+00:00");
+    assert_eq!(plan.slots[0].margin, Duration::minutes(150));
+    assert_eq!(plan.slots[1].start.to_rfc3339(), "2025-10-20T04:00:00+00:00");
+    assert_eq!(plan.slots.last().unwrap().margin, Duration::zero());
+}
+
+#[test]
+fn test_handle_meet_follows_dst() {
+    // Europe is back on standard time a week before New York
+    let hours = WorkingHours::parse("09:00-17:00").unwrap();
+    let before = handle_meet(&words("Europe/Berlin, New York"), Some("2025-10-20"), hours, Duration::hours(1), &clock()).unwrap();
+    let between = handle_meet(&words("Europe/Berlin, New York"), Some("2025-10-27"), hours, Duration::hours(1), &clock()).unwrap();
+
+    assert_eq!(before.windows[0].1 - before.windows[0].0, Duration::hours(2));
+    assert_eq!(between.windows[0].1 - between.windows[0].0, Duration::hours(3));
+    assert_eq!(before.participants[1].label, "New York");
+}
+
+#[test]
+fn test_handle_meet_without_overlap() {
+    let plan = handle_meet(&words("WIB JST PST"), Some("2025-10-20"), WorkingHours::default(), Duration::hours(1), &clock()).unwrap();
+    assert!(plan.windows.is_empty());
+    assert!(plan.slots.is_empty());
+
+    // An overlap shorter than the meeting has no slots
+    let hours = WorkingHours::parse("09:00-12:00").unwrap();
+    let plan = handle_meet(&words("WIB JST"), Some("2025-10-20"), hours, Duration::hours(2), &clock()).unwrap();
+    assert_eq!(plan.windows.len(), 1);
+    assert!(plan.slots.is_empty());
+
+    assert!(matches!(
+        handle_meet(&words("WIB"), Some("2025-13-01"), hours, Duration::hours(1), &clock()),
+        Err(TimecalcError::InvalidDate(_))
+    ));
+    assert!(matches!(
+        handle_meet(&[], None, hours, Duration::hours(1), &clock()),
+        Err(TimecalcError::MissingArgument { .. })
+    ));
+}
+
+#[test]
+fn test_handle_day_of_week_relative() {
+    let info = handle_day_of_week(&words("next friday"), &clock()).unwrap();
+    assert_eq!(info.date, NaiveDate::from_ymd_opt(2025, 3, 7).unwrap());
+
+    let info = handle_day_of_week(&words("end of month"), &clock()).unwrap();
+    assert_eq!(info.weekday, Weekday::Fri);
+
+    assert_eq!(
+        handle_day_of_week(&words("next blursday"), &clock()),
+        Err(TimecalcError::InvalidDate("next blursday".to_string()))
+    );
+}
+
+#[test]
+fn test_handle_timezone_convert_relative_source_out_of_range() {
+    assert_eq!(
+        handle_timezone_convert(&words("now+100000000d WIB to UTC"), &clock()),
+        Err(TimecalcError::InvalidDateTime("now+100000000d WIB".to_string()))
+    );
+}
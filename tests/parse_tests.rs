@@ -1,5 +1,10 @@
 use timecalc::*;
-use chrono::{Local, NaiveDate, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+// Pin "today" so date defaults are deterministic
+fn clock() -> FixedClock {
+    FixedClock::parse("2025-02-27 09:30").unwrap()
+}
 
 #[test]
 fn test_parse_days_numeric() {
@@ -46,6 +51,20 @@ fn test_parse_days_edge_cases() {
     assert_eq!(parse_days(&args), Some(1000));
 }
 
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("3d"), Some(Duration::days(3)));
+    assert_eq!(parse_duration("2h"), Some(Duration::hours(2)));
+    assert_eq!(parse_duration("90m"), Some(Duration::minutes(90)));
+    assert_eq!(parse_duration("1w"), Some(Duration::weeks(1)));
+    assert_eq!(parse_duration("45s"), Some(Duration::seconds(45)));
+    assert_eq!(parse_duration("90 minutes"), Some(Duration::minutes(90)));
+    assert_eq!(parse_duration(" 3 Hours "), Some(Duration::hours(3)));
+    assert_eq!(parse_duration("3"), None);
+    assert_eq!(parse_duration("3y"), None);
+    assert_eq!(parse_duration("soon"), None);
+}
+
 #[test]
 fn test_extract_time_simple() {
     assert_eq!(extract_time("4:00"), NaiveTime::from_hms_opt(4, 0, 0));
@@ -89,121 +108,121 @@ fn test_extract_time_edge_cases() {
 
 #[test]
 fn test_extract_date_formats() {
-    let today = Local::now().date_naive();
+    let today = NaiveDate::from_ymd_opt(2025, 2, 27).unwrap();
 
     // YYYY-MM-DD
     assert_eq!(
-        extract_date("2025-10-31 04:00pm"),
+        extract_date("2025-10-31 04:00pm", &clock()),
         NaiveDate::from_ymd_opt(2025, 10, 31).unwrap()
     );
 
     // Month Day, Year
     assert_eq!(
-        extract_date("october 9, 2025 04:00am"),
+        extract_date("october 9, 2025 04:00am", &clock()),
         NaiveDate::from_ymd_opt(2025, 10, 9).unwrap()
     );
 
     // Short month
     assert_eq!(
-        extract_date("jan 1, 2024"),
+        extract_date("jan 1, 2024", &clock()),
         NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
     );
 
     // Default to today
-    assert_eq!(extract_date("4:00pm"), today);
+    assert_eq!(extract_date("4:00pm", &clock()), today);
 }
 
 #[test]
 fn test_extract_date_various_months() {
     // Test all month names
     assert_eq!(
-        extract_date("january 15, 2025"),
+        extract_date("january 15, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
     );
     assert_eq!(
-        extract_date("february 20, 2025"),
+        extract_date("february 20, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 2, 20).unwrap()
     );
     assert_eq!(
-        extract_date("march 10, 2025"),
+        extract_date("march 10, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
     );
     assert_eq!(
-        extract_date("april 5, 2025"),
+        extract_date("april 5, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 4, 5).unwrap()
     );
     assert_eq!(
-        extract_date("may 1, 2025"),
+        extract_date("may 1, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 5, 1).unwrap()
     );
     assert_eq!(
-        extract_date("june 30, 2025"),
+        extract_date("june 30, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 6, 30).unwrap()
     );
     assert_eq!(
-        extract_date("july 4, 2025"),
+        extract_date("july 4, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 7, 4).unwrap()
     );
     assert_eq!(
-        extract_date("august 15, 2025"),
+        extract_date("august 15, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 8, 15).unwrap()
     );
     assert_eq!(
-        extract_date("september 1, 2025"),
+        extract_date("september 1, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()
     );
     assert_eq!(
-        extract_date("november 11, 2025"),
+        extract_date("november 11, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 11, 11).unwrap()
     );
     assert_eq!(
-        extract_date("december 25, 2025"),
+        extract_date("december 25, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()
     );
 
     // Test short month names
     assert_eq!(
-        extract_date("feb 14, 2025"),
+        extract_date("feb 14, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 2, 14).unwrap()
     );
     assert_eq!(
-        extract_date("mar 17, 2025"),
+        extract_date("mar 17, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 3, 17).unwrap()
     );
     assert_eq!(
-        extract_date("apr 1, 2025"),
+        extract_date("apr 1, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 4, 1).unwrap()
     );
     assert_eq!(
-        extract_date("jun 15, 2025"),
+        extract_date("jun 15, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
     );
     assert_eq!(
-        extract_date("jul 20, 2025"),
+        extract_date("jul 20, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 7, 20).unwrap()
     );
     assert_eq!(
-        extract_date("aug 31, 2025"),
+        extract_date("aug 31, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 8, 31).unwrap()
     );
     assert_eq!(
-        extract_date("sep 5, 2025"),
+        extract_date("sep 5, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 9, 5).unwrap()
     );
     assert_eq!(
-        extract_date("sept 10, 2025"),
+        extract_date("sept 10, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 9, 10).unwrap()
     );
     assert_eq!(
-        extract_date("oct 31, 2025"),
+        extract_date("oct 31, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 10, 31).unwrap()
     );
     assert_eq!(
-        extract_date("nov 5, 2025"),
+        extract_date("nov 5, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 11, 5).unwrap()
     );
     assert_eq!(
-        extract_date("dec 31, 2025"),
+        extract_date("dec 31, 2025", &clock()),
         NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()
     );
 }
@@ -212,15 +231,15 @@ fn test_extract_date_various_months() {
 fn test_extract_date_iso_format() {
     // Test YYYY-MM-DD format parsing
     assert_eq!(
-        extract_date("2025-01-01"),
+        extract_date("2025-01-01", &clock()),
         NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
     );
     assert_eq!(
-        extract_date("2025-12-31"),
+        extract_date("2025-12-31", &clock()),
         NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()
     );
     assert_eq!(
-        extract_date("2024-02-29"),
+        extract_date("2024-02-29", &clock()),
         NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
     );
 }
@@ -228,51 +247,52 @@ fn test_extract_date_iso_format() {
 #[test]
 fn test_extract_date_invalid_dates() {
     // Invalid dates should fall back to today
-    let today = Local::now().date_naive();
+    let today = NaiveDate::from_ymd_opt(2025, 2, 27).unwrap();
 
     // February 30th doesn't exist - should default to today
-    assert_eq!(extract_date("february 30, 2025"), today);
+    assert_eq!(extract_date("february 30, 2025", &clock()), today);
 
     // Month 13 doesn't exist
-    assert_eq!(extract_date("month13 15, 2025"), today);
+    assert_eq!(extract_date("month13 15, 2025", &clock()), today);
 }
 
 #[test]
 fn test_parse_flexible_datetime() {
     // Simple time today
-    let result = parse_flexible_datetime("4:00");
+    let result = parse_flexible_datetime("4:00", &clock());
     assert!(result.is_some());
     let dt = result.unwrap();
+    assert_eq!(dt.date(), NaiveDate::from_ymd_opt(2025, 2, 27).unwrap());
     assert_eq!(dt.time(), NaiveTime::from_hms_opt(4, 0, 0).unwrap());
 
     // With date
-    let result = parse_flexible_datetime("october 9, 2025 04:00am");
+    let result = parse_flexible_datetime("october 9, 2025 04:00am", &clock());
     assert!(result.is_some());
     let dt = result.unwrap();
     assert_eq!(dt.date(), NaiveDate::from_ymd_opt(2025, 10, 9).unwrap());
     assert_eq!(dt.time(), NaiveTime::from_hms_opt(4, 0, 0).unwrap());
 
     // With "at" keyword
-    let result = parse_flexible_datetime("october 9, 2025 at 04:00am");
+    let result = parse_flexible_datetime("october 9, 2025 at 04:00am", &clock());
     assert!(result.is_some());
     let dt = result.unwrap();
     assert_eq!(dt.date(), NaiveDate::from_ymd_opt(2025, 10, 9).unwrap());
     assert_eq!(dt.time(), NaiveTime::from_hms_opt(4, 0, 0).unwrap());
 
     // Invalid time returns None
-    assert!(parse_flexible_datetime("invalid").is_none());
+    assert!(parse_flexible_datetime("invalid", &clock()).is_none());
 }
 
 #[test]
 fn test_parse_flexible_datetime_edge_cases() {
     // Test with PM time
-    let result = parse_flexible_datetime("2:30pm");
+    let result = parse_flexible_datetime("2:30pm", &clock());
     assert!(result.is_some());
     let dt = result.unwrap();
     assert_eq!(dt.time(), NaiveTime::from_hms_opt(14, 30, 0).unwrap());
 
     // Test with ISO date
-    let result = parse_flexible_datetime("2025-12-25 10:00");
+    let result = parse_flexible_datetime("2025-12-25 10:00", &clock());
     assert!(result.is_some());
     let dt = result.unwrap();
     assert_eq!(dt.date(), NaiveDate::from_ymd_opt(2025, 12, 25).unwrap());
@@ -286,7 +306,7 @@ fn test_parse_datetime_and_tz() {
         "04:00AM".to_string(),
         "UTC+8".to_string(),
     ];
-    let (dt, tz) = parse_datetime_and_tz(&parts);
+    let (dt, tz) = parse_datetime_and_tz(&parts, &clock());
     assert!(dt.is_some());
     assert_eq!(tz, "UTC+8");
 
@@ -299,19 +319,70 @@ fn test_parse_datetime_and_tz() {
         "04:00AM".to_string(),
         "UTC+8".to_string(),
     ];
-    let (dt, tz) = parse_datetime_and_tz(&parts);
+    let (dt, tz) = parse_datetime_and_tz(&parts, &clock());
     assert!(dt.is_some());
     assert_eq!(tz, "UTC+8");
 
     // Empty input
     let parts: Vec<String> = vec![];
-    let (dt, tz) = parse_datetime_and_tz(&parts);
+    let (dt, tz) = parse_datetime_and_tz(&parts, &clock());
     assert!(dt.is_none());
     assert_eq!(tz, "");
 
     // Single element
     let parts = vec!["UTC+8".to_string()];
-    let (dt, tz) = parse_datetime_and_tz(&parts);
+    let (dt, tz) = parse_datetime_and_tz(&parts, &clock());
     assert!(dt.is_none());
     assert_eq!(tz, "UTC+8");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_parse_date_relative() {
+    // The clock is pinned to Thursday, February 27, 2025
+    let date = |input: &str| parse_date(input, &clock());
+    let day = |d: u32| NaiveDate::from_ymd_opt(2025, 2, d);
+    let march = |d: u32| NaiveDate::from_ymd_opt(2025, 3, d);
+
+    assert_eq!(date("today"), day(27));
+    assert_eq!(date("Tomorrow"), day(28));
+    assert_eq!(date("yesterday"), day(26));
+    assert_eq!(date("friday"), day(28));
+    assert_eq!(date("thursday"), day(27));
+    assert_eq!(date("this saturday"), march(1));
+    assert_eq!(date("next monday"), march(3));
+    // "last" is the most recent one before today, not the one of last week
+    assert_eq!(date("last tue"), day(25));
+    assert_eq!(date("last thursday"), day(20));
+    assert_eq!(date("last friday"), day(21));
+    let wednesday = FixedClock::parse("2025-10-15 09:00").unwrap();
+    assert_eq!(parse_date("last monday", &wednesday), NaiveDate::from_ymd_opt(2025, 10, 13));
+    assert_eq!(date("in 2 weeks"), march(13));
+    assert_eq!(date("in 1 month"), march(27));
+    assert_eq!(date("3 days ago"), day(24));
+    assert_eq!(date("1 year ago"), NaiveDate::from_ymd_opt(2024, 2, 27));
+    assert_eq!(date("end of month"), day(28));
+    assert_eq!(date("end of the year"), NaiveDate::from_ymd_opt(2025, 12, 31));
+
+    // Explicit dates still win, and nothing recognisable is no date
+    assert_eq!(date("2025-12-25"), NaiveDate::from_ymd_opt(2025, 12, 25));
+    assert_eq!(date("october 9, 2025"), NaiveDate::from_ymd_opt(2025, 10, 9));
+    assert_eq!(date("end of month"), Some(extract_date("end of month 17:00", &clock())));
+    assert_eq!(date("someday"), None);
+    assert_eq!(date("month"), None);
+}
+
+#[test]
+fn test_parse_date_week_start() {
+    config::install(Config::parse("week_start = \"sunday\"").unwrap());
+
+    // Weeks run Sunday to Saturday: this one started on February 23
+    assert_eq!(parse_date("this sunday", &clock()), NaiveDate::from_ymd_opt(2025, 2, 23));
+    assert_eq!(parse_date("next sunday", &clock()), NaiveDate::from_ymd_opt(2025, 3, 2));
+    assert_eq!(parse_date("end of week", &clock()), NaiveDate::from_ymd_opt(2025, 3, 1));
+}
+
+#[test]
+fn test_parse_flexible_datetime_relative_date() {
+    let dt = parse_flexible_datetime("next friday at 4:00pm", &clock()).unwrap();
+    assert_eq!(dt, NaiveDate::from_ymd_opt(2025, 3, 7).unwrap().and_hms_opt(16, 0, 0).unwrap());
+}
@@ -0,0 +1,82 @@
+use chrono::{NaiveDate, TimeZone, Weekday};
+use chrono_tz::Tz;
+use timecalc::*;
+
+#[test]
+fn test_render_conversion() {
+    let from = Zone::parse_offset("UTC+8").unwrap().with_ymd_and_hms(2025, 10, 9, 4, 0, 0).unwrap();
+    let conv = Conversion {
+        from,
+        from_label: "UTC+8".to_string(),
+        to: from.with_timezone(&Zone::from(Tz::Asia__Jakarta)),
+        to_label: "WIB".to_string(),
+        warnings: vec![],
+        dst: None,
+        also: vec![],
+    };
+
+    let out = render::conversion(&conv);
+    assert!(out.starts_with("\nTIMEZONE CONVERSION\n"));
+    assert!(out.contains("FROM: Thursday, October 09, 2025 04:00 UTC+8 (UTC+08:00)\n"));
+    assert!(out.contains("TO:   Thursday, October 09, 2025 03:00 WIB (UTC+07:00)  -1h\n"));
+    assert!(out.ends_with("=====\n\n"));
+}
+
+#[test]
+fn test_render_remaining() {
+    let rem = RemainingDays {
+        zone: Zone::from(Tz::Asia__Jakarta),
+        period: Period::Year,
+        today: NaiveDate::from_ymd_opt(2025, 12, 1).unwrap(),
+        end: NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+        remaining: 30,
+        passed: 335,
+    };
+
+    let out = render::remaining(&rem);
+    assert!(out.contains("ZONE:            Asia/Jakarta (local)\n"));
+    assert!(out.contains("END OF YEAR:     December 31, 2025\n"));
+    assert!(out.contains("DAYS REMAINING:  30 days\n"));
+    assert!(out.contains("DAYS PASSED:     335 days\n"));
+}
+
+#[test]
+fn test_render_day_info() {
+    let info = DayInfo {
+        date: NaiveDate::from_ymd_opt(2025, 12, 25).unwrap(),
+        weekday: Weekday::Thu,
+    };
+
+    let out = render::day_info(&info);
+    assert!(out.contains("DATE: December 25, 2025\n"));
+    assert!(out.contains("DAY:  Thursday\n"));
+}
+
+#[test]
+fn test_render_json_remaining() {
+    let report = render::Report::Remaining(RemainingDays {
+        zone: Zone::parse_offset("UTC+7").unwrap(),
+        period: Period::Month,
+        today: NaiveDate::from_ymd_opt(2025, 2, 27).unwrap(),
+        end: NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+        remaining: 1,
+        passed: 27,
+    });
+
+    let value = render::json(&report);
+    assert_eq!(value["command"], "remaining");
+    assert_eq!(value["today"], "2025-02-27");
+    assert_eq!(value["days_remaining"], 1);
+    assert_eq!(value["zone"], "UTC+07:00");
+
+    let out = render::render(&report, render::OutputFormat::Json);
+    assert!(out.ends_with("}\n"));
+    assert!(render::render(&report, render::OutputFormat::Text).contains("DAYS REMAINING:  1 days"));
+}
+
+#[test]
+fn test_output_format_from_str() {
+    assert_eq!("json".parse::<render::OutputFormat>(), Ok(render::OutputFormat::Json));
+    assert_eq!("TEXT".parse::<render::OutputFormat>(), Ok(render::OutputFormat::Text));
+    assert!("yaml".parse::<render::OutputFormat>().is_err());
+}
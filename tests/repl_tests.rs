@@ -0,0 +1,94 @@
+use chrono::NaiveDate;
+use timecalc::render::OutputFormat;
+use timecalc::repl::Session;
+use timecalc::*;
+
+fn clock() -> FixedClock {
+    FixedClock::parse("2025-02-27 09:30").unwrap()
+}
+
+#[test]
+fn test_repl_runs_cli_grammar() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    let out = session.eval("tz 2025-10-09 04:00 UTC+8 to WIB").unwrap();
+    assert!(out.contains("TO:   Thursday, October 09, 2025 03:00 WIB"));
+
+    let last = session.last().unwrap();
+    assert_eq!(last.datetime.to_string(), "2025-10-09 03:00:00");
+    assert_eq!(last.zone, Some((Zone::Named(chrono_tz::Asia::Jakarta), "WIB".to_string())));
+}
+
+#[test]
+fn test_repl_previous_result_to_zone() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    session.eval("tz 2025-10-09 04:00 UTC+8 to WIB").unwrap();
+    let out = session.eval("_ to JST").unwrap();
+    assert!(out.contains("TO:   Thursday, October 09, 2025 05:00 JST"));
+}
+
+#[test]
+fn test_repl_previous_result_in_a_country() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    session.eval("tz 2025-10-09 10:00 WIB to Germany").unwrap();
+    assert_eq!(session.eval("_").unwrap(), "Thursday, October 09, 2025 05:00 Germany (DE)\n");
+    let out = session.eval("_ to WIB").unwrap();
+    assert!(out.contains("FROM: Thursday, October 09, 2025 05:00 CEST (UTC+02:00) [Europe/Berlin]"));
+    assert!(out.contains("TO:   Thursday, October 09, 2025 10:00 WIB (UTC+07:00)"));
+}
+
+#[test]
+fn test_repl_previous_result_arithmetic() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    session.eval("tz 2025-10-09 04:00 UTC+8 to WIB").unwrap();
+    assert_eq!(session.eval("_ + 3d").unwrap(), "Sunday, October 12, 2025 03:00 WIB\n");
+    assert_eq!(session.eval("_ - 4h").unwrap(), "Saturday, October 11, 2025 23:00 WIB\n");
+    assert_eq!(session.eval("_").unwrap(), "Saturday, October 11, 2025 23:00 WIB\n");
+}
+
+#[test]
+fn test_repl_previous_date_has_no_zone() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    session.eval("future 3").unwrap();
+    let last = session.last().unwrap();
+    assert_eq!(last.datetime.date(), NaiveDate::from_ymd_opt(2025, 3, 2).unwrap());
+    assert_eq!(last.zone, None);
+
+    assert!(session.eval("_ to JST").unwrap_err().contains("no timezone"));
+    assert_eq!(session.eval("_ + 1w").unwrap(), "Sunday, March 09, 2025\n");
+}
+
+#[test]
+fn test_repl_errors() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    assert!(session.eval("_").unwrap_err().contains("no previous result"));
+    assert!(session.eval("bogus").unwrap_err().contains("unrecognized subcommand"));
+    assert!(session.eval("repl").unwrap_err().contains("cannot be used here"));
+    assert!(session.eval("day nope").unwrap_err().starts_with("ERROR: Invalid date format"));
+
+    session.eval("day 2025-12-25").unwrap();
+    assert!(session.eval("_ + soon").unwrap_err().contains("Could not parse duration"));
+    assert_eq!(session.eval("_ + 100000000d").unwrap_err(), "ERROR: '_ + 100000000d' is out of range");
+    // The session and its previous result survive
+    assert_eq!(session.eval("_").unwrap(), "Thursday, December 25, 2025\n");
+}
+
+#[test]
+fn test_repl_json_per_line() {
+    let clock = clock();
+    let mut session = Session::new(&clock, OutputFormat::Text);
+
+    let out = session.eval("day 2025-12-25 --output json").unwrap();
+    assert_eq!(out, "{\"command\":\"day\",\"date\":\"2025-12-25\",\"weekday\":\"Thursday\"}\n");
+}
@@ -1,3 +1,4 @@
+use chrono::FixedOffset;
 use timecalc::*;
 
 #[test]
@@ -72,4 +73,291 @@ fn test_parse_timezone_all_supported() {
     // JST
     assert!(parse_timezone("JST").is_some());
     assert!(parse_timezone("jst").is_some());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_parse_timezone_iana_names() {
+    assert_eq!(parse_timezone("Asia/Jakarta"), Some(chrono_tz::Asia::Jakarta.into()));
+    assert_eq!(parse_timezone("europe/berlin"), Some(chrono_tz::Europe::Berlin.into()));
+    assert_eq!(parse_timezone("AMERICA/SAO_PAULO"), Some(chrono_tz::America::Sao_Paulo.into()));
+    assert_eq!(parse_timezone("America/Sao Paulo"), Some(chrono_tz::America::Sao_Paulo.into()));
+    assert!(parse_timezone("Europe/Atlantis").is_none());
+}
+
+#[test]
+fn test_parse_timezone_aliases_win_over_iana() {
+    // "EST" is also an IANA name (fixed UTC-5), the alias follows DST
+    assert_eq!(parse_timezone("EST"), Some(chrono_tz::America::New_York.into()));
+}
+
+#[test]
+fn test_zone_label() {
+    assert_eq!(zone_label("wib", chrono_tz::Asia::Jakarta.into()), "WIB");
+    assert_eq!(zone_label("america/sao paulo", chrono_tz::America::Sao_Paulo.into()), "America/Sao_Paulo");
+}
+
+#[test]
+fn test_suggest_timezones() {
+    assert_eq!(suggest_timezones("Europe/Berln").first().map(String::as_str), Some("Europe/Berlin"));
+    assert!(suggest_timezones("tokio").contains(&"Asia/Tokyo".to_string()));
+    assert!(suggest_timezones("qqqqqqqq").is_empty());
+}
+
+#[test]
+fn test_parse_timezone_fixed_offsets() {
+    let offset = |hours: i32, minutes: i32| {
+        let sign = if hours < 0 { -1 } else { 1 };
+        Some(Zone::Fixed(FixedOffset::east_opt(hours * 3600 + sign * minutes * 60).unwrap()))
+    };
+
+    assert_eq!(parse_timezone("UTC+7"), offset(7, 0));
+    assert_eq!(parse_timezone("utc+07:00"), offset(7, 0));
+    assert_eq!(parse_timezone("GMT-3"), offset(-3, 0));
+    assert_eq!(parse_timezone("UTC+5:30"), offset(5, 30));
+    assert_eq!(parse_timezone("+0545"), offset(5, 45));
+    assert_eq!(parse_timezone("-03:30"), offset(-3, 30));
+    assert_eq!(parse_timezone("UTC+14"), offset(14, 0));
+    assert_eq!(parse_timezone("UTC+15"), None);
+    assert_eq!(parse_timezone("UTC+5:75"), None);
+}
+
+#[test]
+fn test_parse_timezone_etc_gmt() {
+    // The POSIX sign convention goes through the IANA table untouched
+    assert_eq!(parse_timezone("Etc/GMT-7"), Some(chrono_tz::Etc::GMTMinus7.into()));
+    assert_eq!(parse_timezone("Etc/GMT+3"), Some(chrono_tz::Etc::GMTPlus3.into()));
+}
+
+#[test]
+fn test_zone_name() {
+    assert_eq!(parse_timezone("UTC-3:30").unwrap().name(), "UTC-03:30");
+    assert_eq!(parse_timezone("wib").unwrap().name(), "Asia/Jakarta");
+}
+
+#[test]
+fn test_parse_timezone_cities() {
+    assert_eq!(parse_timezone("Tokyo"), Some(chrono_tz::Asia::Tokyo.into()));
+    assert_eq!(parse_timezone("new york"), Some(chrono_tz::America::New_York.into()));
+    assert_eq!(parse_timezone("San Francisco"), Some(chrono_tz::America::Los_Angeles.into()));
+
+    // Indonesian cities land in WIB, WITA or WIT
+    assert_eq!(parse_timezone("Bandung"), Some(chrono_tz::Asia::Jakarta.into()));
+    assert_eq!(parse_timezone("denpasar"), Some(chrono_tz::Asia::Makassar.into()));
+    assert_eq!(parse_timezone("Ambon"), Some(chrono_tz::Asia::Jayapura.into()));
+}
+
+#[test]
+fn test_parse_timezone_countries() {
+    assert_eq!(parse_timezone("JP"), Some(chrono_tz::Asia::Tokyo.into()));
+    assert_eq!(parse_timezone("Germany"), Some(chrono_tz::Europe::Berlin.into()));
+    assert_eq!(parse_timezone("UK"), Some(chrono_tz::Europe::London.into()));
+
+    // Codes only match upper-case, and never words like "to" or "am"
+    assert!(parse_timezone("jp").is_none());
+    assert!(parse_timezone("AM").is_none());
+    assert!(parse_timezone("TO").is_none());
+}
+
+#[test]
+fn test_resolve_timezone_multi_zone_country() {
+    match resolve_timezone("ID") {
+        Err(TimecalcError::AmbiguousTimezone { name, candidates }) => {
+            assert_eq!(name, "ID");
+            assert_eq!(candidates, vec!["Asia/Jakarta", "Asia/Makassar", "Asia/Jayapura"]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(resolve_timezone("Mars"), Err(TimecalcError::UnsupportedTimezone("Mars".to_string())));
+}
+
+#[test]
+fn test_parse_timezone_abbreviations() {
+    assert_eq!(parse_timezone("PDT"), Some(chrono_tz::America::Los_Angeles.into()));
+    assert_eq!(parse_timezone("cest"), Some(chrono_tz::Europe::Berlin.into()));
+    assert_eq!(parse_timezone("AEST"), Some(chrono_tz::Australia::Sydney.into()));
+    assert!(abbreviation_warning("PDT").is_none());
+}
+
+#[test]
+fn test_parse_timezone_ambiguous_abbreviations_default() {
+    assert_eq!(parse_timezone("CST"), Some(chrono_tz::America::Chicago.into()));
+    assert_eq!(parse_timezone("IST"), Some(chrono_tz::Asia::Kolkata.into()));
+    assert_eq!(parse_timezone("BST"), Some(chrono_tz::Europe::London.into()));
+
+    let warning = abbreviation_warning("ist").unwrap();
+    assert!(warning.contains("using Asia/Kolkata (India)"));
+    assert!(warning.contains("Europe/Dublin (Ireland), Asia/Jerusalem (Israel)"));
+}
+
+fn local(s: &str) -> chrono::NaiveDateTime {
+    chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+}
+
+#[test]
+fn test_localize_regular_time_has_no_note() {
+    let (dt, note) = localize(chrono_tz::America::Los_Angeles.into(), local("2025-07-01 02:30"), DstPolicy::Reject).unwrap();
+    assert_eq!(dt.to_rfc3339(), "2025-07-01T02:30:00-07:00");
+    assert!(note.is_none());
+}
+
+#[test]
+fn test_localize_gap_policies() {
+    let zone: Zone = chrono_tz::America::Los_Angeles.into();
+    let gap = local("2025-03-09 02:30");
+    let utc = |policy| localize(zone, gap, policy).unwrap().0.naive_utc().format("%H:%M").to_string();
+
+    // Earliest and latest are the earlier and later instant, as for repeated times
+    assert_eq!(utc(DstPolicy::Earliest), "09:30");
+    assert_eq!(utc(DstPolicy::Latest), "10:30");
+    assert_eq!(utc(DstPolicy::ShiftForward), "10:00");
+
+    let (_, note) = localize(zone, gap, DstPolicy::ShiftForward).unwrap();
+    assert_eq!(
+        note.unwrap(),
+        "02:30 does not exist in America/Los_Angeles on 2025-03-09, clocks skip from 02:00 to 03:00 \
+         (PST -> PDT); using 03:00 PDT (--dst shift-forward)"
+    );
+    assert!(matches!(localize(zone, gap, DstPolicy::Reject), Err(TimecalcError::DstTransition(_))));
+}
+
+#[test]
+fn test_localize_overlap_policies() {
+    let zone: Zone = chrono_tz::Europe::Berlin.into();
+    let repeated = local("2025-10-26 02:30");
+
+    let (earliest, note) = localize(zone, repeated, DstPolicy::Earliest).unwrap();
+    assert_eq!(earliest.to_rfc3339(), "2025-10-26T02:30:00+02:00");
+    assert_eq!(
+        note.unwrap(),
+        "02:30 happens twice in Europe/Berlin on 2025-10-26, clocks go back from 03:00 to 02:00 \
+         (CEST -> CET); using the first one, 02:30 CEST (--dst earliest)"
+    );
+    let (latest, _) = localize(zone, repeated, DstPolicy::Latest).unwrap();
+    assert_eq!(latest.to_rfc3339(), "2025-10-26T02:30:00+01:00");
+
+    let err = localize(zone, repeated, DstPolicy::Reject).unwrap_err();
+    assert_eq!(err.exit_code(), 12);
+}
+
+#[test]
+fn test_localize_fixed_offset_never_adjusts() {
+    let (_, note) = localize(Zone::parse_offset("UTC-8").unwrap(), local("2025-03-09 02:30"), DstPolicy::Reject).unwrap();
+    assert!(note.is_none());
+}
+
+fn utc(s: &str) -> chrono::DateTime<chrono::Utc> {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_zone_transitions_around_an_instant() {
+    let zone: Zone = chrono_tz::America::Los_Angeles.into();
+
+    let next = zone.next_transition(utc("2025-10-09T00:00:00Z")).unwrap();
+    assert_eq!(next.at, utc("2025-11-02T09:00:00Z"));
+    assert_eq!((next.before.to_string(), next.after.to_string()), ("PDT".to_string(), "PST".to_string()));
+    assert_eq!(next.local_before().to_string(), "2025-11-02 02:00:00");
+    assert_eq!(next.local_after().to_string(), "2025-11-02 01:00:00");
+
+    let previous = zone.previous_transition(utc("2025-10-09T00:00:00Z")).unwrap();
+    assert_eq!(previous.at, utc("2025-03-09T10:00:00Z"));
+    assert!(previous.after.is_dst() && !previous.before.is_dst());
+}
+
+#[test]
+fn test_zone_without_transitions() {
+    let fixed = Zone::parse_offset("UTC+5:30").unwrap();
+    assert!(fixed.next_transition(utc("2025-10-09T00:00:00Z")).is_none());
+    assert!(fixed.previous_transition(utc("2025-10-09T00:00:00Z")).is_none());
+
+    let jakarta: Zone = chrono_tz::Asia::Jakarta.into();
+    assert!(jakarta.next_transition(utc("2025-10-09T00:00:00Z")).is_none());
+    assert_eq!(jakarta.previous_transition(utc("2025-10-09T00:00:00Z")).unwrap().at, utc("1963-12-31T16:30:00Z"));
+}
+
+#[test]
+fn test_zone_transitions_in_range() {
+    let zone: Zone = chrono_tz::Europe::London.into();
+    let transitions = zone.transitions(utc("2024-01-01T00:00:00Z"), utc("2026-01-01T00:00:00Z"));
+
+    let changes: Vec<_> = transitions
+        .iter()
+        .map(|t| format!("{} {}->{}", t.at.format("%Y-%m-%d %H:%M"), t.before, t.after))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            "2024-03-31 01:00 GMT->BST",
+            "2024-10-27 01:00 BST->GMT",
+            "2025-03-30 01:00 GMT->BST",
+            "2025-10-26 01:00 BST->GMT",
+        ]
+    );
+    assert!(zone.transitions(utc("2025-04-01T00:00:00Z"), utc("2025-10-01T00:00:00Z")).is_empty());
+}
+
+#[test]
+fn test_local_keywords() {
+    assert_eq!(parse_timezone("local"), Some(Zone::local()));
+    assert_eq!(parse_timezone("HERE"), Some(Zone::local()));
+    assert!(is_local_keyword(" Local "));
+    assert_eq!(zone_label("here", Zone::local()), Zone::local().name());
+}
+
+#[test]
+fn test_military_zones() {
+    let hours = |tz: &str| match parse_timezone(tz) {
+        Some(Zone::Fixed(offset)) => offset.local_minus_utc() / 3600,
+        other => panic!("{} resolved to {:?}", tz, other),
+    };
+    assert_eq!(hours("A"), 1);
+    assert_eq!(hours("K"), 10);
+    assert_eq!(hours("R"), -5);
+    assert_eq!(hours("romeo"), -5);
+    assert_eq!(hours("Y"), -12);
+    assert_eq!(hours("z"), 0);
+    assert_eq!(parse_timezone("J"), Some(Zone::local()));
+    assert_eq!(parse_timezone("Juliett"), Some(Zone::local()));
+    // Countries and cities keep their names, lower-case letters are not zones
+    assert_eq!(parse_timezone("India"), Some(chrono_tz::Asia::Kolkata.into()));
+    assert_eq!(parse_timezone("Lima"), Some(chrono_tz::America::Lima.into()));
+    assert!(parse_timezone("a").is_none());
+}
+
+#[test]
+fn test_military_letters() {
+    assert_eq!(military_letter(FixedOffset::east_opt(7 * 3600).unwrap()), Some('G'));
+    assert_eq!(military_letter(FixedOffset::west_opt(3 * 3600).unwrap()), Some('P'));
+    assert_eq!(military_letter(FixedOffset::east_opt(0).unwrap()), Some('Z'));
+    assert_eq!(military_letter(FixedOffset::east_opt(5 * 3600 + 1800).unwrap()), None);
+    assert_eq!(military_letter(FixedOffset::east_opt(13 * 3600).unwrap()), None);
+}
+
+#[test]
+fn test_convert_relative_to_now() {
+    let clock = FixedClock::parse("2025-10-09T09:30:00+07:00").unwrap();
+    let convert = |s: &str| {
+        let args: Vec<String> = s.split_whitespace().map(String::from).collect();
+        handle_timezone_convert(&args, &clock)
+    };
+
+    let conv = convert("now WIB to JST").unwrap();
+    assert_eq!(conv.from.to_rfc3339(), "2025-10-09T09:30:00+07:00");
+    assert_eq!(conv.to.to_rfc3339(), "2025-10-09T11:30:00+09:00");
+
+    let conv = convert("in 90 minutes to PST").unwrap();
+    assert_eq!(conv.to.to_rfc3339(), "2025-10-08T21:00:00-07:00");
+
+    let conv = convert("now+3h WIB to UTC").unwrap();
+    assert_eq!(conv.from.to_rfc3339(), "2025-10-09T12:30:00+07:00");
+    assert_eq!(conv.from_label, "WIB");
+    assert!(conv.dst.is_none());
+
+    let conv = convert("now - 30 minutes New York to Tokyo, PST").unwrap();
+    assert_eq!(conv.from_label, "New York");
+    assert_eq!(conv.from.to_rfc3339(), "2025-10-08T22:00:00-04:00");
+    assert_eq!(conv.also.len(), 1);
+
+    assert_eq!(convert("in 3 bananas to JST").unwrap_err(), TimecalcError::InvalidDateTime("in 3 bananas".to_string()));
+    assert!(matches!(convert("now to"), Err(TimecalcError::MissingArgument { .. })));
+}